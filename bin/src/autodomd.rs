@@ -1,9 +1,20 @@
-use clap::{Parser, Subcommand};
+use autodomd_library_common::TaskPriority;
+#[cfg(feature = "generate")]
+use autodomd_library_generator::{OutputFormat, RuleKey};
+#[cfg(feature = "scan")]
+use autodomd_library_graph::FolderRules;
+#[cfg(all(feature = "scan", any(feature = "generate", feature = "plan")))]
+use autodomd_library_metadata::EffortScale;
+#[cfg(feature = "generate")]
+use clap::builder::PossibleValuesParser;
+use clap::{Args, Parser, Subcommand};
 
 /// AutoDomd - Automated TODO management system
 #[derive(Parser)]
 #[command(name = "autodomd")]
-#[command(about = "Scan source code and markdown files for TODO items and generate comprehensive task lists")]
+#[command(
+    about = "Scan source code and markdown files for TODO items and generate comprehensive task lists"
+)]
 #[command(version)]
 struct Cli {
     /// Increase verbosity
@@ -35,6 +46,13 @@ enum Commands {
         /// Maximum scan depth
         #[arg(long)]
         max_depth: Option<usize>,
+
+        /// Exit with an error when any task is past its due date
+        #[arg(long)]
+        fail_on_overdue: bool,
+
+        #[command(flatten)]
+        scan: ScanArgs,
     },
     /// Generate TODO.md from scanned items
     Generate {
@@ -45,9 +63,207 @@ enum Commands {
         /// Skip auto-generated header
         #[arg(long)]
         no_header: bool,
+
+        /// Output format (written to TODO.md, TODO.json, TODO.sarif, ... unless --output is given)
+        #[arg(long, value_name = "FORMAT", default_value = "markdown")]
+        #[cfg_attr(
+            feature = "generate",
+            arg(value_parser = PossibleValuesParser::new(OutputFormat::VARIANTS))
+        )]
+        format: String,
+
+        /// Columns for csv/tsv output, comma-separated; `custom.KEY` adds a frontmatter key
+        #[arg(long, value_name = "COLUMNS", value_delimiter = ',')]
+        columns: Vec<String>,
+
+        /// Rule of each sarif, checkstyle or codequality entry
+        #[arg(long, value_name = "KEY", default_value = "tag")]
+        #[cfg_attr(
+            feature = "generate",
+            arg(value_parser = PossibleValuesParser::new(RuleKey::VARIANTS))
+        )]
+        rule_by: String,
+
+        /// Only include code tasks with this tag (repeatable)
+        #[arg(long = "only-tag", value_name = "NAME")]
+        only_tags: Vec<String>,
+
+        /// URL template for issue links, e.g. https://tracker/{id}
+        #[arg(long = "issue-url", value_name = "TEMPLATE")]
+        issue_url_template: Option<String>,
+
+        /// URL template for source links in the HTML report, e.g. https://host/repo/blob/main/{path}#L{line}
+        #[arg(long = "source-url", value_name = "TEMPLATE")]
+        source_url_template: Option<String>,
+
+        /// Exit with an error (after writing TODO.md) when any task is past its due date
+        #[arg(long)]
+        fail_on_overdue: bool,
+
+        /// Leave tasks with status `done` out of TODO.md
+        #[arg(long)]
+        hide_done: bool,
+
+        /// Embed the dependency graph in TODO.md as a Mermaid flowchart
+        #[arg(long)]
+        mermaid: bool,
+
+        /// Also write the dependency graph as a Graphviz DOT file
+        #[arg(long, value_name = "FILE")]
+        dot: Option<std::path::PathBuf>,
+
+        #[command(flatten)]
+        scan: ScanArgs,
+
+        #[command(flatten)]
+        effort: EffortArgs,
+    },
+    /// Show the critical path, tasks ready to start and what each task unblocks
+    Plan {
+        #[command(flatten)]
+        scan: ScanArgs,
+
+        #[command(flatten)]
+        effort: EffortArgs,
     },
 }
 
+/// Flags that control how the project is scanned, shared by `scan`, `generate` and `plan`
+#[derive(Args)]
+struct ScanArgs {
+    /// Additional tag keyword to recognize, as NAME or NAME=PRIORITY (repeatable)
+    #[arg(long = "tag", value_name = "NAME[=PRIORITY]", value_parser = parse_tag_spec)]
+    tags: Vec<(String, TaskPriority)>,
+
+    /// `.mailmap`-style file mapping assignee handles to canonical owners
+    #[arg(long)]
+    owners_file: Option<std::path::PathBuf>,
+
+    /// TOML file with additional `[[language]]` definitions
+    #[arg(long)]
+    languages_file: Option<std::path::PathBuf>,
+
+    /// Skip paths matching this glob, e.g. `vendor/` (repeatable; adds to `.autodomdignore`)
+    #[arg(long = "ignore", value_name = "PATTERN")]
+    ignore_patterns: Vec<String>,
+
+    /// Only accept TODOs that follow the exact grammar and warn about near-misses
+    #[arg(long)]
+    strict: bool,
+
+    /// Don't link task files to their folder's index task or order them by `NNN-` prefix
+    #[arg(long)]
+    no_folder_rules: bool,
+
+    /// File name (without `.md`) marking a folder's index task (repeatable; default: index, README)
+    #[arg(long = "index-name", value_name = "NAME")]
+    index_names: Vec<String>,
+}
+
+impl ScanArgs {
+    /// Scan configuration for these flags
+    #[cfg(feature = "scan")]
+    fn into_config(self, verbose: bool) -> autodomd_command_scan::ScanCommandConfig {
+        autodomd_command_scan::ScanCommandConfig {
+            custom_tags: self.tags,
+            owners_file: self.owners_file,
+            languages_file: self.languages_file,
+            ignore_patterns: self.ignore_patterns,
+            strict: self.strict,
+            folder_rules: folder_rules(self.no_folder_rules, self.index_names),
+            verbose,
+            ..Default::default()
+        }
+    }
+}
+
+/// Flags that convert effort estimates, shared by `generate` and `plan`
+#[derive(Args)]
+struct EffortArgs {
+    /// Working hours in a day when converting effort estimates (default: 8)
+    #[arg(long, value_name = "HOURS")]
+    hours_per_day: Option<f64>,
+
+    /// Hours of work in one story point (default: 4)
+    #[arg(long, value_name = "HOURS")]
+    hours_per_point: Option<f64>,
+
+    /// Story points for a t-shirt size, e.g. `xl=8` (repeatable)
+    #[arg(long = "effort-size", value_name = "SIZE=POINTS", value_parser = parse_size_spec)]
+    effort_sizes: Vec<(String, f64)>,
+}
+
+impl EffortArgs {
+    /// Effort scale for these flags
+    #[cfg(all(feature = "scan", any(feature = "generate", feature = "plan")))]
+    fn into_scale(self) -> EffortScale {
+        let mut scale = EffortScale::default();
+        if let Some(hours) = self.hours_per_day {
+            scale.hours_per_day = hours;
+        }
+        if let Some(hours) = self.hours_per_point {
+            scale.hours_per_point = hours;
+        }
+        for (name, points) in self.effort_sizes {
+            scale.set_size(&name, points);
+        }
+        scale
+    }
+}
+
+/// Parse a `NAME` or `NAME=PRIORITY` tag specification
+fn parse_tag_spec(spec: &str) -> Result<(String, TaskPriority), String> {
+    let (name, priority) = match spec.split_once('=') {
+        Some((name, priority)) => {
+            let priority = TaskPriority::from_name(priority).ok_or_else(|| {
+                format!(
+                    "invalid priority '{}' (expected high, medium or low)",
+                    priority
+                )
+            })?;
+            (name.trim(), priority)
+        }
+        None => (spec.trim(), TaskPriority::Medium),
+    };
+
+    if name.is_empty() {
+        return Err("tag name must not be empty".to_string());
+    }
+    Ok((name.to_string(), priority))
+}
+
+/// Parse a `SIZE=POINTS` effort size specification
+fn parse_size_spec(spec: &str) -> Result<(String, f64), String> {
+    let (name, points) = spec
+        .split_once('=')
+        .ok_or_else(|| format!("invalid effort size '{}' (expected SIZE=POINTS)", spec))?;
+    let points: f64 = points
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid story points '{}' for size '{}'", points, name))?;
+
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("effort size name must not be empty".to_string());
+    }
+    Ok((name.to_string(), points))
+}
+
+/// Build the folder rules from the `--no-folder-rules` and `--index-name` flags
+#[cfg(feature = "scan")]
+fn folder_rules(disabled: bool, index_names: Vec<String>) -> FolderRules {
+    if disabled {
+        return FolderRules::disabled();
+    }
+
+    let mut rules = FolderRules::default();
+    if !index_names.is_empty() {
+        rules.index_names = index_names;
+    }
+    rules
+}
+
+#[cfg_attr(not(any(feature = "init", feature = "scan")), allow(unreachable_code))]
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
 
@@ -56,7 +272,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("autodomd v{}", env!("CARGO_PKG_VERSION"));
     }
 
+    // Each arm binds every flag, but only uses them when its command is compiled in
     match cli.command {
+        #[cfg_attr(not(feature = "init"), allow(unused_variables))]
         Commands::Init { no_samples } => {
             #[cfg(feature = "init")]
             {
@@ -71,7 +289,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         println!("  Created todo/ directory");
                     }
                     if !result.sample_files_created.is_empty() {
-                        println!("  Created {} sample files", result.sample_files_created.len());
+                        println!(
+                            "  Created {} sample files",
+                            result.sample_files_created.len()
+                        );
                     }
                 }
             }
@@ -80,20 +301,31 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 return Err("Init command not available - compiled without 'init' feature".into());
             }
         }
-        Commands::Scan { root, follow_links, max_depth } => {
+        #[cfg_attr(not(feature = "scan"), allow(unused_variables))]
+        Commands::Scan {
+            root,
+            follow_links,
+            max_depth,
+            fail_on_overdue,
+            scan,
+        } => {
             #[cfg(feature = "scan")]
             {
                 let config = autodomd_command_scan::ScanCommandConfig {
                     root_path: root,
                     follow_links,
                     max_depth,
-                    verbose: cli.verbose,
+                    fail_on_overdue,
+                    ..scan.into_config(cli.verbose)
                 };
                 let result = autodomd_command_scan::run_scan(&config)?;
                 if !cli.verbose {
                     println!("✅ Scan complete!");
-                    println!("  Found {} tasks in {} files", result.tasks_found,
-                           result.markdown_files_scanned + result.source_files_scanned);
+                    println!(
+                        "  Found {} tasks in {} files",
+                        result.tasks_found,
+                        result.markdown_files_scanned + result.source_files_scanned
+                    );
                 }
             }
             #[cfg(not(feature = "scan"))]
@@ -101,36 +333,70 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 return Err("Scan command not available - compiled without 'scan' feature".into());
             }
         }
-        Commands::Generate { output, no_header } => {
+        #[cfg_attr(
+            not(all(feature = "scan", feature = "generate")),
+            allow(unused_variables)
+        )]
+        Commands::Generate {
+            output,
+            no_header,
+            format,
+            columns,
+            rule_by,
+            only_tags,
+            issue_url_template,
+            source_url_template,
+            fail_on_overdue,
+            hide_done,
+            mermaid,
+            dot,
+            scan,
+            effort,
+        } => {
             #[cfg(feature = "scan")]
             {
                 // First scan for tasks
-                let scan_config = autodomd_command_scan::ScanCommandConfig {
-                    verbose: cli.verbose,
-                    ..Default::default()
-                };
-
+                let scan_config = scan.into_config(cli.verbose);
                 let scan_result = autodomd_command_scan::run_scan(&scan_config)?;
 
                 #[cfg(feature = "generate")]
                 {
                     let gen_config = autodomd_command_generate::GenerateCommandConfig {
                         output_path: output,
+                        format: format.parse()?,
+                        root_path: scan_config.root_path.clone(),
                         include_header: !no_header,
+                        tag_filter: only_tags,
+                        issue_url_template,
+                        source_url_template,
+                        columns,
+                        rule_key: rule_by.parse()?,
+                        hide_done,
+                        folder_rules: scan_config.folder_rules.clone(),
+                        include_mermaid: mermaid,
+                        dot_output: dot,
+                        effort_scale: effort.into_scale(),
+                        fail_on_overdue,
                         verbose: cli.verbose,
-                        ..Default::default()
                     };
 
-                    let result = autodomd_command_generate::run_generate(&scan_result.tasks, &gen_config)?;
+                    let result =
+                        autodomd_command_generate::run_generate(&scan_result.tasks, &gen_config)?;
                     if !cli.verbose {
-                        println!("✅ TODO.md generated successfully!");
+                        println!(
+                            "✅ {} generated successfully!",
+                            result.output_path.display()
+                        );
                         println!("  Created: {}", result.output_path.display());
                         println!("  Tasks documented: {}", result.tasks_written);
                     }
                 }
                 #[cfg(not(feature = "generate"))]
                 {
-                    return Err("Generate command not available - compiled without 'generate' feature".into());
+                    return Err(
+                        "Generate command not available - compiled without 'generate' feature"
+                            .into(),
+                    );
                 }
             }
             #[cfg(not(feature = "scan"))]
@@ -138,8 +404,37 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 return Err("Generate command requires scan feature".into());
             }
         }
+        #[cfg_attr(not(all(feature = "scan", feature = "plan")), allow(unused_variables))]
+        Commands::Plan { scan, effort } => {
+            #[cfg(feature = "scan")]
+            {
+                let scan_config = scan.into_config(cli.verbose);
+                let scan_result = autodomd_command_scan::run_scan(&scan_config)?;
+
+                #[cfg(feature = "plan")]
+                {
+                    let plan_config = autodomd_command_plan::PlanCommandConfig {
+                        folder_rules: scan_config.folder_rules.clone(),
+                        effort_scale: effort.into_scale(),
+                        verbose: cli.verbose,
+                    };
+
+                    let result = autodomd_command_plan::run_plan(&scan_result.tasks, &plan_config)?;
+                    print!("{}", result.report);
+                }
+                #[cfg(not(feature = "plan"))]
+                {
+                    return Err(
+                        "Plan command not available - compiled without 'plan' feature".into(),
+                    );
+                }
+            }
+            #[cfg(not(feature = "scan"))]
+            {
+                return Err("Plan command requires scan feature".into());
+            }
+        }
     };
 
     Ok(())
 }
-
@@ -5,18 +5,46 @@
 
 use std::path::PathBuf;
 
-use autodomd_library_common::{TaskCollection, TodoResult};
+use autodomd_library_common::{TaskCollection, TodoError, TodoResult};
 use autodomd_library_generator::{generate_todo_md, GeneratorConfig};
+use autodomd_library_graph::FolderRules;
+use autodomd_library_metadata::EffortScale;
+
+pub use autodomd_library_generator::{OutputFormat, RuleKey};
 
 /// Configuration for the generate command
 #[derive(Debug, Clone)]
 pub struct GenerateCommandConfig {
-    /// Output file path (defaults to "TODO.md")
+    /// Output file path (defaults to "TODO.md", or `TODO.<format>` for other formats)
     pub output_path: Option<PathBuf>,
+    /// File format to write
+    pub format: OutputFormat,
     /// Project root path for relative paths
     pub root_path: Option<PathBuf>,
     /// Whether to include auto-generated header
     pub include_header: bool,
+    /// Only include code tasks with these tags (empty includes all tasks)
+    pub tag_filter: Vec<String>,
+    /// URL template for issue links, with `{id}` replaced by the issue identifier
+    pub issue_url_template: Option<String>,
+    /// URL template for source links in the HTML report, with `{path}` and `{line}` placeholders
+    pub source_url_template: Option<String>,
+    /// Leave tasks with status `done` out of the output
+    pub hide_done: bool,
+    /// Folder conventions for ordering task files and linking them to their folder's task
+    pub folder_rules: FolderRules,
+    /// Embed the dependency graph in TODO.md as a Mermaid flowchart
+    pub include_mermaid: bool,
+    /// Also write the dependency graph to this path as Graphviz DOT
+    pub dot_output: Option<PathBuf>,
+    /// Conversion used to add up effort estimates
+    pub effort_scale: EffortScale,
+    /// Columns of the CSV/TSV export (empty for the default set)
+    pub columns: Vec<String>,
+    /// What report formats (SARIF, Checkstyle, Code Quality) use as the rule of each task
+    pub rule_key: RuleKey,
+    /// Fail after writing the output when any task is past its due date
+    pub fail_on_overdue: bool,
     /// Whether to output verbose information
     pub verbose: bool,
 }
@@ -25,8 +53,20 @@ impl Default for GenerateCommandConfig {
     fn default() -> Self {
         Self {
             output_path: Some(PathBuf::from("TODO.md")),
+            format: OutputFormat::Markdown,
             root_path: Some(PathBuf::from(".")),
             include_header: true,
+            tag_filter: Vec::new(),
+            issue_url_template: None,
+            source_url_template: None,
+            hide_done: false,
+            folder_rules: FolderRules::default(),
+            include_mermaid: false,
+            dot_output: None,
+            effort_scale: EffortScale::default(),
+            columns: Vec::new(),
+            rule_key: RuleKey::default(),
+            fail_on_overdue: false,
             verbose: false,
         }
     }
@@ -36,9 +76,23 @@ impl GenerateCommandConfig {
     /// Convert to GeneratorConfig for the generator library
     fn to_generator_config(&self) -> GeneratorConfig {
         GeneratorConfig {
-            output_path: self.output_path.clone().unwrap_or_else(|| PathBuf::from("TODO.md")),
+            output_path: self
+                .output_path
+                .clone()
+                .unwrap_or_else(|| self.format.default_output_path()),
+            format: self.format,
             root_path: self.root_path.clone().unwrap_or_else(|| PathBuf::from(".")),
             include_header: self.include_header,
+            tag_filter: self.tag_filter.clone(),
+            issue_url_template: self.issue_url_template.clone(),
+            source_url_template: self.source_url_template.clone(),
+            hide_done: self.hide_done,
+            folder_rules: self.folder_rules.clone(),
+            include_mermaid: self.include_mermaid,
+            dot_output: self.dot_output.clone(),
+            effort_scale: self.effort_scale.clone(),
+            columns: self.columns.clone(),
+            rule_key: self.rule_key,
         }
     }
 }
@@ -53,20 +107,26 @@ pub struct GenerateResult {
 }
 
 /// Execute the generate command
-pub fn run_generate(tasks: &TaskCollection, config: &GenerateCommandConfig) -> TodoResult<GenerateResult> {
+pub fn run_generate(
+    tasks: &TaskCollection,
+    config: &GenerateCommandConfig,
+) -> TodoResult<GenerateResult> {
     if config.verbose {
         println!("Generating TODO.md...");
         println!("Tasks to process: {}", tasks.len());
-        println!("Output path: {}", config.to_generator_config().output_path.display());
+        println!(
+            "Output path: {}",
+            config.to_generator_config().output_path.display()
+        );
     }
 
     // Generate the TODO.md file
     let generator_config = config.to_generator_config();
-    generate_todo_md(tasks, &generator_config)?;
+    let tasks_written = generate_todo_md(tasks, &generator_config)?;
 
     let result = GenerateResult {
         output_path: generator_config.output_path,
-        tasks_written: tasks.len(),
+        tasks_written,
     };
 
     if config.verbose {
@@ -75,5 +135,28 @@ pub fn run_generate(tasks: &TaskCollection, config: &GenerateCommandConfig) -> T
         println!("  Tasks written: {}", result.tasks_written);
     }
 
+    if config.fail_on_overdue {
+        ensure_none_overdue(tasks)?;
+    }
+
     Ok(result)
 }
+
+/// Print every overdue task and fail with [`TodoError::Overdue`] if there are any
+fn ensure_none_overdue(tasks: &TaskCollection) -> TodoResult<()> {
+    let overdue = tasks.overdue_tasks(chrono::Local::now().date_naive());
+    for task in &overdue {
+        if let Some(due_date) = task.due_date {
+            eprintln!(
+                "Overdue: {} ({}, due {})",
+                task.title, task.location, due_date
+            );
+        }
+    }
+
+    if overdue.is_empty() {
+        Ok(())
+    } else {
+        Err(TodoError::Overdue(overdue.len()))
+    }
+}
@@ -0,0 +1,95 @@
+//! Plan command for autodomd
+//!
+//! This microcrate implements the plan subcommand, which answers the sprint
+//! planning questions from the dependency graph: the longest chain of remaining
+//! work, what can start right now, and what each task unblocks.
+
+use autodomd_library_common::{Task, TaskCollection, TodoResult};
+use autodomd_library_graph::{FolderRules, TaskGraph};
+use autodomd_library_metadata::EffortScale;
+
+/// Configuration for the plan command
+#[derive(Debug, Clone, Default)]
+pub struct PlanCommandConfig {
+    /// Folder conventions for linking task files to their folder's task
+    pub folder_rules: FolderRules,
+    /// Conversion used to weigh tasks by their effort estimates
+    pub effort_scale: EffortScale,
+    /// Whether to output verbose information
+    pub verbose: bool,
+}
+
+/// Result of a plan operation
+#[derive(Debug)]
+pub struct PlanResult {
+    /// The plan as printable text
+    pub report: String,
+    /// Number of tasks on the critical path
+    pub critical_path_tasks: usize,
+    /// Number of tasks ready to start
+    pub ready_tasks: usize,
+}
+
+/// Execute the plan command
+pub fn run_plan(tasks: &TaskCollection, config: &PlanCommandConfig) -> TodoResult<PlanResult> {
+    if config.verbose {
+        println!("Planning {} tasks...", tasks.len());
+    }
+
+    let graph = TaskGraph::build_with_rules(tasks.all_tasks(), &config.folder_rules);
+    // Unestimated tasks count as one story point so they still lengthen a chain
+    let scale = &config.effort_scale;
+    let plan = graph.plan(|task| scale.task_hours(task).unwrap_or(scale.hours_per_point));
+    let mut report = String::new();
+
+    report.push_str(&format!(
+        "Critical path ({}, effort {}):\n",
+        tasks_count(plan.critical_path.len()),
+        scale.format(plan.critical_path_effort)
+    ));
+    for (i, task) in plan.critical_path.iter().enumerate() {
+        report.push_str(&format!("  {}. {}\n", i + 1, task_line(task)));
+    }
+
+    report.push_str(&format!("\nReady to start ({}):\n", plan.ready.len()));
+    if plan.ready.is_empty() {
+        report.push_str("  (none)\n");
+    }
+    for task in &plan.ready {
+        report.push_str(&format!("  - {}\n", task_line(task)));
+    }
+
+    report.push_str("\nUnblocks:\n");
+    if plan.unblocks.is_empty() {
+        report.push_str("  (none)\n");
+    }
+    for unblock in &plan.unblocks {
+        report.push_str(&format!(
+            "  - {}: {} downstream, effort {}\n",
+            task_line(unblock.task),
+            tasks_count(unblock.downstream_tasks),
+            scale.format(unblock.downstream_effort)
+        ));
+    }
+
+    Ok(PlanResult {
+        report,
+        critical_path_tasks: plan.critical_path.len(),
+        ready_tasks: plan.ready.len(),
+    })
+}
+
+/// One task as `id - title (path)`
+fn task_line(task: &Task) -> String {
+    format!(
+        "{} - {} ({})",
+        task.id,
+        task.title,
+        task.location.file_path.display()
+    )
+}
+
+/// `1 task`, `3 tasks`
+fn tasks_count(count: usize) -> String {
+    format!("{} task{}", count, if count == 1 { "" } else { "s" })
+}
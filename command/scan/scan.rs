@@ -5,9 +5,13 @@
 
 use std::path::PathBuf;
 
-use autodomd_library_common::{TaskCollection, TodoResult};
+use autodomd_library_common::{TaskCollection, TaskPriority, TodoError, TodoResult};
+use autodomd_library_graph::{FolderRules, TaskGraph};
+use autodomd_library_language::LanguageRegistry;
+use autodomd_library_parser::{
+    parse_markdown_files, parse_source_files, OwnerAliases, ParserConfig,
+};
 use autodomd_library_scanner::{scan_all_files, ScanConfig};
-use autodomd_library_parser::{parse_markdown_files, parse_source_files};
 
 /// Configuration for the scan command
 #[derive(Debug, Clone)]
@@ -18,6 +22,20 @@ pub struct ScanCommandConfig {
     pub follow_links: bool,
     /// Maximum scan depth
     pub max_depth: Option<usize>,
+    /// Additional tag keywords (beyond TODO, FIXME, ...) and their default priorities
+    pub custom_tags: Vec<(String, TaskPriority)>,
+    /// `.mailmap`-style file mapping assignee handles to canonical owners
+    pub owners_file: Option<PathBuf>,
+    /// TOML file with additional `[[language]]` definitions
+    pub languages_file: Option<PathBuf>,
+    /// Glob patterns for vendored code and fixtures to skip
+    pub ignore_patterns: Vec<String>,
+    /// Only accept TODOs that follow the strict grammar, warning about near-misses
+    pub strict: bool,
+    /// Folder conventions for linking task files to their folder's task
+    pub folder_rules: FolderRules,
+    /// Fail the scan when any task is past its due date
+    pub fail_on_overdue: bool,
     /// Whether to output verbose information
     pub verbose: bool,
 }
@@ -28,19 +46,53 @@ impl Default for ScanCommandConfig {
             root_path: None,
             follow_links: false,
             max_depth: Some(10),
+            custom_tags: Vec::new(),
+            owners_file: None,
+            languages_file: None,
+            ignore_patterns: Vec::new(),
+            strict: false,
+            folder_rules: FolderRules::default(),
+            fail_on_overdue: false,
             verbose: false,
         }
     }
 }
 
 impl ScanCommandConfig {
+    /// Build the language registry shared by the scanner and parser
+    fn to_language_registry(&self) -> TodoResult<LanguageRegistry> {
+        let mut languages = LanguageRegistry::default();
+        if let Some(languages_file) = &self.languages_file {
+            languages.register_file(languages_file)?;
+        }
+        Ok(languages)
+    }
+
     /// Convert to ScanConfig for the scanner library
-    fn to_scan_config(&self) -> ScanConfig {
+    fn to_scan_config(&self, languages: &LanguageRegistry) -> ScanConfig {
         ScanConfig {
             root_path: self.root_path.clone().unwrap_or_else(|| PathBuf::from(".")),
             follow_links: self.follow_links,
             max_depth: self.max_depth,
+            languages: languages.clone(),
+            ignore_patterns: self.ignore_patterns.clone(),
+        }
+    }
+
+    /// Convert to ParserConfig for the parser library
+    fn to_parser_config(&self, languages: &LanguageRegistry) -> TodoResult<ParserConfig> {
+        let mut parser_config = ParserConfig {
+            languages: languages.clone(),
+            strict: self.strict,
+            ..Default::default()
+        };
+        for (name, priority) in &self.custom_tags {
+            parser_config.tags.register(name.clone(), *priority);
+        }
+        if let Some(owners_file) = &self.owners_file {
+            parser_config.owner_aliases = OwnerAliases::from_file(owners_file)?;
         }
+        Ok(parser_config)
     }
 }
 
@@ -59,13 +111,16 @@ pub struct ScanResult {
 
 /// Execute the scan command
 pub fn run_scan(config: &ScanCommandConfig) -> TodoResult<ScanResult> {
+    let languages = config.to_language_registry()?;
+    let scan_config = config.to_scan_config(&languages);
+    let parser_config = config.to_parser_config(&languages)?;
+
     if config.verbose {
         println!("Starting scan...");
-        println!("Root path: {}", config.to_scan_config().root_path.display());
+        println!("Root path: {}", scan_config.root_path.display());
     }
 
     // Scan for files
-    let scan_config = config.to_scan_config();
     let (markdown_files, source_files) = scan_all_files(&scan_config)?;
 
     if config.verbose {
@@ -81,11 +136,26 @@ pub fn run_scan(config: &ScanCommandConfig) -> TodoResult<ScanResult> {
     all_tasks.extend(markdown_tasks);
 
     // Parse source files
-    let source_tasks = parse_source_files(&source_files)?;
+    let source_tasks = parse_source_files(&source_files, &parser_config)?;
     all_tasks.extend(source_tasks);
 
-    // Sort tasks for consistent output
+    // Sort tasks for consistent output, then settle duplicate IDs in that order
     all_tasks.sort();
+    for (duplicate, unique) in all_tasks.ensure_unique_ids() {
+        if let Some(task) = all_tasks.get(&unique) {
+            eprintln!(
+                "Warning: duplicate task id `{}` at {}; using `{}`",
+                duplicate, task.location, unique
+            );
+        }
+    }
+
+    // Report broken or inconsistent task relationships
+    for problem in
+        TaskGraph::build_with_rules(all_tasks.all_tasks(), &config.folder_rules).problems()
+    {
+        eprintln!("Warning: {}", problem);
+    }
 
     let tasks_found = all_tasks.len();
 
@@ -103,5 +173,28 @@ pub fn run_scan(config: &ScanCommandConfig) -> TodoResult<ScanResult> {
         println!("  Tasks found: {}", result.tasks_found);
     }
 
+    if config.fail_on_overdue {
+        ensure_none_overdue(&result.tasks)?;
+    }
+
     Ok(result)
 }
+
+/// Print every overdue task and fail with [`TodoError::Overdue`] if there are any
+fn ensure_none_overdue(tasks: &TaskCollection) -> TodoResult<()> {
+    let overdue = tasks.overdue_tasks(chrono::Local::now().date_naive());
+    for task in &overdue {
+        if let Some(due_date) = task.due_date {
+            eprintln!(
+                "Overdue: {} ({}, due {})",
+                task.title, task.location, due_date
+            );
+        }
+    }
+
+    if overdue.is_empty() {
+        Ok(())
+    } else {
+        Err(TodoError::Overdue(overdue.len()))
+    }
+}
@@ -3,11 +3,16 @@
 //! This microcrate contains shared types, error handling, and utility functions
 //! used across the autodomd project.
 
+use std::collections::BTreeMap;
 use std::fmt;
 use std::path::PathBuf;
 
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 /// Represents the source of a TODO task
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum TaskSource {
     /// Task from a markdown file in todo/ directory
     Markdown,
@@ -16,12 +21,17 @@ pub enum TaskSource {
 }
 
 /// Represents the location of a TODO task
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TaskLocation {
     /// Path to the file containing the task
     pub file_path: PathBuf,
     /// Line number where the task was found (1-indexed)
     pub line_number: Option<usize>,
+    /// Last line covered by the task when it spans several lines (1-indexed)
+    pub end_line_number: Option<usize>,
+    /// Column of the task's tag on its first line (1-indexed, in characters)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column_number: Option<usize>,
 }
 
 impl TaskLocation {
@@ -30,6 +40,8 @@ impl TaskLocation {
         Self {
             file_path,
             line_number,
+            end_line_number: line_number,
+            column_number: None,
         }
     }
 
@@ -42,42 +54,172 @@ impl TaskLocation {
     pub fn from_source_file(file_path: PathBuf, line_number: usize) -> Self {
         Self::new(file_path, Some(line_number))
     }
+
+    /// Create a TaskLocation for source code spanning a range of lines
+    pub fn from_source_range(file_path: PathBuf, start_line: usize, end_line: usize) -> Self {
+        Self {
+            file_path,
+            line_number: Some(start_line),
+            end_line_number: Some(end_line.max(start_line)),
+            column_number: None,
+        }
+    }
+
+    /// Set the column where the task starts
+    pub fn with_column(mut self, column: usize) -> Self {
+        self.column_number = Some(column);
+        self
+    }
+
+    /// Whether this location covers more than a single line
+    pub fn is_multi_line(&self) -> bool {
+        matches!(
+            (self.line_number, self.end_line_number),
+            (Some(start), Some(end)) if end > start
+        )
+    }
+}
+
+impl fmt::Display for TaskLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line_number, self.end_line_number) {
+            (Some(start), Some(end)) if end > start => {
+                write!(f, "{}:{}-{}", self.file_path.display(), start, end)
+            }
+            (Some(line), _) => write!(f, "{}:{}", self.file_path.display(), line),
+            (None, _) => write!(f, "{}", self.file_path.display()),
+        }
+    }
 }
 
 /// Represents a category for grouping tasks
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+///
+/// Categories form a tree: `TODO(Backend/Auth)` and `todo/backend/auth/*.md`
+/// both belong to `Auth` nested under `Backend`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum TaskCategory {
     /// Uncategorized tasks
     General,
-    /// Custom category from TODO(Category) syntax
-    Custom(String),
+    /// Custom category path from TODO(Category/Subcategory) syntax, outermost first
+    Custom(Vec<String>),
 }
 
 /// Priority levels for task organization
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum TaskPriority {
     Low,
     Medium,
     High,
 }
 
+impl TaskPriority {
+    /// Parse a priority name (case-insensitive), e.g. "high"
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "high" => Some(TaskPriority::High),
+            "medium" => Some(TaskPriority::Medium),
+            "low" => Some(TaskPriority::Low),
+            _ => None,
+        }
+    }
+
+    /// Get the display name for this priority
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            TaskPriority::Low => "Low",
+            TaskPriority::Medium => "Medium",
+            TaskPriority::High => "High",
+        }
+    }
+}
+
+impl fmt::Display for TaskPriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+/// Workflow status of a task, in the order status sections are listed
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum TaskStatus {
+    InProgress,
+    Blocked,
+    #[default]
+    Open,
+    Done,
+}
+
+impl TaskStatus {
+    /// Parse a status name (case-insensitive), e.g. "in-progress" or "wip"
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().replace(['_', ' '], "-").as_str() {
+            "open" | "todo" => Some(TaskStatus::Open),
+            "in-progress" | "wip" | "active" => Some(TaskStatus::InProgress),
+            "blocked" => Some(TaskStatus::Blocked),
+            "done" | "closed" | "complete" | "completed" => Some(TaskStatus::Done),
+            _ => None,
+        }
+    }
+
+    /// Get the display name for this status
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            TaskStatus::Open => "Open",
+            TaskStatus::InProgress => "In Progress",
+            TaskStatus::Blocked => "Blocked",
+            TaskStatus::Done => "Done",
+        }
+    }
+}
+
+impl fmt::Display for TaskStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
 impl TaskCategory {
-    /// Create a TaskCategory from a string
+    /// Create a TaskCategory from a string, splitting `Backend/Auth` into a path
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
-        if s.is_empty() {
+        Self::from_segments(s.split('/'))
+    }
+
+    /// Create a TaskCategory from path segments, outermost first
+    pub fn from_segments<I, S>(segments: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let segments: Vec<String> = segments
+            .into_iter()
+            .map(|segment| segment.as_ref().trim().to_string())
+            .filter(|segment| !segment.is_empty())
+            .collect();
+
+        if segments.is_empty() {
             TaskCategory::General
         } else {
-            TaskCategory::Custom(s.to_string())
+            TaskCategory::Custom(segments)
         }
     }
 
-    /// Get the display name for this category
-    pub fn display_name(&self) -> &str {
+    /// Path segments of this category, outermost first (`["General"]` for uncategorized tasks)
+    pub fn segments(&self) -> Vec<&str> {
         match self {
-            TaskCategory::General => "General",
-            TaskCategory::Custom(name) => name,
+            TaskCategory::General => vec!["General"],
+            TaskCategory::Custom(segments) => segments.iter().map(String::as_str).collect(),
         }
     }
+
+    /// Get the display name for this category, e.g. "Backend/Auth"
+    pub fn display_name(&self) -> String {
+        self.segments().join("/")
+    }
 }
 
 impl fmt::Display for TaskCategory {
@@ -86,9 +228,58 @@ impl fmt::Display for TaskCategory {
     }
 }
 
+/// Categories serialize as their display name, e.g. `"Backend/Auth"`
+impl Serialize for TaskCategory {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.display_name())
+    }
+}
+
+impl<'de> Deserialize<'de> for TaskCategory {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        if name == "General" {
+            Ok(TaskCategory::General)
+        } else {
+            Ok(TaskCategory::from_str(&name))
+        }
+    }
+}
+
+/// Structured metadata from a task file's frontmatter
+///
+/// Keys that set a [`Task`] field (`id`, `priority`, `status`, `issues`, ...)
+/// are stored on the task itself, not here.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskMetadata {
+    /// Tasks that must be done first, from the `dependencies` key
+    pub dependencies: Vec<String>,
+    /// Tasks waiting on this one, from the `blocks` key
+    pub blocks: Vec<String>,
+    /// Effort estimate from the `effort` (or legacy `estimated_effort`) key
+    pub effort: Option<String>,
+    /// Free-form labels from the `tags` key
+    pub tags: Vec<String>,
+    /// Any other keys, with values rendered as text
+    pub custom: BTreeMap<String, String>,
+}
+
+/// A checklist item (`- [ ]` / `- [x]`) inside a markdown task file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Subtask {
+    /// Text of the checklist item
+    pub title: String,
+    /// Whether the item is checked off
+    pub done: bool,
+    /// Line number in the task file (1-indexed)
+    pub line_number: usize,
+}
+
 /// Represents a single TODO task
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Task {
+    /// Stable identifier used to reference the task (see [`task_slug`])
+    pub id: String,
     /// The title/description of the task
     pub title: String,
     /// The category this task belongs to
@@ -99,6 +290,22 @@ pub struct Task {
     pub location: TaskLocation,
     /// The source type of this task
     pub source: TaskSource,
+    /// Comment tag keyword that introduced a code task (e.g. TODO, FIXME)
+    pub tag: Option<String>,
+    /// Owner responsible for the task, from `TODO(@owner)` syntax
+    pub assignee: Option<String>,
+    /// Issue-tracker references (e.g. `123`, `PROJ-45`)
+    pub issues: Vec<String>,
+    /// Date by which the task should be done
+    pub due_date: Option<NaiveDate>,
+    /// Frontmatter metadata of a markdown task (empty for code tasks)
+    pub metadata: TaskMetadata,
+    /// Checklist items of a markdown task (empty for code tasks)
+    pub subtasks: Vec<Subtask>,
+    /// Workflow status (open unless a marker or `status:` key says otherwise)
+    pub status: TaskStatus,
+    /// Why the task is blocked, if known
+    pub blocked_reason: Option<String>,
 }
 
 impl Task {
@@ -111,11 +318,20 @@ impl Task {
         source: TaskSource,
     ) -> Self {
         Self {
+            id: String::new(),
             title,
             category,
             priority,
             location,
             source,
+            tag: None,
+            assignee: None,
+            issues: Vec::new(),
+            due_date: None,
+            metadata: TaskMetadata::default(),
+            subtasks: Vec::new(),
+            status: TaskStatus::Open,
+            blocked_reason: None,
         }
     }
 
@@ -163,28 +379,26 @@ impl Task {
     }
 }
 
+impl Task {
+    /// Whether the task is unfinished and its due date has passed as of `today`
+    pub fn is_overdue(&self, today: NaiveDate) -> bool {
+        self.status != TaskStatus::Done && self.due_date.is_some_and(|due| due < today)
+    }
+
+    /// Number of checked-off subtasks
+    pub fn completed_subtasks(&self) -> usize {
+        self.subtasks.iter().filter(|subtask| subtask.done).count()
+    }
+}
+
 impl fmt::Display for Task {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match &self.location.line_number {
-            Some(line) => write!(
-                f,
-                "- [ ] {} ({}:{})",
-                self.title,
-                self.location.file_path.display(),
-                line
-            ),
-            None => write!(
-                f,
-                "- [ ] {} ({})",
-                self.title,
-                self.location.file_path.display()
-            ),
-        }
+        write!(f, "- [ ] {} ({})", self.title, self.location)
     }
 }
 
 /// Collection of tasks with utilities for management
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TaskCollection {
     pub tasks: Vec<Task>,
 }
@@ -220,6 +434,45 @@ impl TaskCollection {
         self.tasks.is_empty()
     }
 
+    /// Get tasks whose due date has passed as of `today`, earliest due first
+    pub fn overdue_tasks(&self, today: NaiveDate) -> Vec<&Task> {
+        let mut overdue: Vec<&Task> = self
+            .tasks
+            .iter()
+            .filter(|task| task.is_overdue(today))
+            .collect();
+        overdue.sort_by_key(|task| task.due_date);
+        overdue
+    }
+
+    /// Find a task by its ID
+    pub fn get(&self, id: &str) -> Option<&Task> {
+        self.tasks.iter().find(|task| task.id == id)
+    }
+
+    /// Make task IDs unique by suffixing repeats with `-2`, `-3`, ...,
+    /// returning each renamed ID with its replacement
+    ///
+    /// Tasks keep their position, so call this after [`TaskCollection::sort`]
+    /// for IDs that do not depend on scan order.
+    pub fn ensure_unique_ids(&mut self) -> Vec<(String, String)> {
+        let mut seen: std::collections::HashSet<String> = std::collections::HashSet::new();
+        let mut renamed = Vec::new();
+        for task in &mut self.tasks {
+            if seen.insert(task.id.clone()) {
+                continue;
+            }
+
+            let unique = (2..)
+                .map(|n| format!("{}-{}", task.id, n))
+                .find(|candidate| !seen.contains(candidate))
+                .unwrap_or_default();
+            seen.insert(unique.clone());
+            renamed.push((std::mem::replace(&mut task.id, unique.clone()), unique));
+        }
+        renamed
+    }
+
     /// Sort tasks by file path and line number for deterministic output
     pub fn sort(&mut self) {
         self.tasks.sort_by(|a, b| {
@@ -232,6 +485,29 @@ impl TaskCollection {
     }
 }
 
+/// Turn a name into a task ID slug, dropping a numeric ordering prefix
+///
+/// `010-Core Scanning` becomes `core-scanning`, so task files can be reordered
+/// by renaming without breaking references to them.
+pub fn task_slug(name: &str) -> String {
+    let without_order = match name.split_once('-') {
+        Some((order, rest)) if !order.is_empty() && order.chars().all(|c| c.is_ascii_digit()) => {
+            rest
+        }
+        _ => name,
+    };
+
+    let mut slug = String::new();
+    for c in without_order.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
 /// Custom error type for autodomd operations
 #[derive(Debug, thiserror::Error)]
 pub enum TodoError {
@@ -246,6 +522,9 @@ pub enum TodoError {
 
     #[error("Invalid file format: {0}")]
     InvalidFormat(String),
+
+    #[error("{0} task(s) are past their due date")]
+    Overdue(usize),
 }
 
 pub type TodoResult<T> = Result<T, TodoError>;
@@ -3,50 +3,294 @@
 //! This microcrate provides functionality for generating the final TODO.md file
 //! from parsed TODO items and tasks.
 
-use std::collections::HashMap;
+mod generator_categories;
+mod generator_checkstyle;
+mod generator_codequality;
+mod generator_html;
+mod generator_json;
+mod generator_sarif;
+mod generator_table;
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 use std::fs;
+use std::str::FromStr;
+
+use autodomd_library_common::{Task, TaskCollection, TaskSource, TaskStatus, TodoResult};
+use autodomd_library_graph::{FolderRules, TaskGraph};
+use autodomd_library_metadata::{parse_frontmatter, EffortScale, EffortTotal};
+use chrono::{DateTime, NaiveDate, Utc};
+
+use generator_categories::CategoryTree;
+use generator_checkstyle::generate_checkstyle_content;
+use generator_codequality::generate_codequality_content;
+use generator_html::generate_html_content;
+use generator_json::generate_json_content;
+use generator_sarif::generate_sarif_content;
+use generator_table::generate_table_content;
+
+pub use generator_json::JSON_SCHEMA_VERSION;
+pub use generator_table::{DEFAULT_TABLE_COLUMNS, TABLE_COLUMNS};
+
+/// File format written by [`generate_todo_md`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// The human-readable TODO.md
+    #[default]
+    Markdown,
+    /// A versioned JSON document for dashboards and bots
+    Json,
+    /// A self-contained HTML report with filters and search
+    Html,
+    /// Comma-separated values, one row per task
+    Csv,
+    /// Tab-separated values, one row per task
+    Tsv,
+    /// A SARIF 2.1.0 log of the code TODOs for code-scanning dashboards
+    Sarif,
+    /// A Checkstyle XML report of the code TODOs for CI warning collectors
+    Checkstyle,
+    /// A GitLab Code Quality report of the code TODOs for merge-request widgets
+    CodeQuality,
+}
+
+impl OutputFormat {
+    /// Names accepted by [`OutputFormat::from_str`]
+    pub const VARIANTS: &'static [&'static str] = &[
+        "markdown",
+        "json",
+        "html",
+        "csv",
+        "tsv",
+        "sarif",
+        "checkstyle",
+        "codequality",
+    ];
+
+    /// Name of the format, as accepted by [`OutputFormat::from_str`]
+    pub fn name(&self) -> &'static str {
+        match self {
+            OutputFormat::Markdown => "markdown",
+            OutputFormat::Json => "json",
+            OutputFormat::Html => "html",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Tsv => "tsv",
+            OutputFormat::Sarif => "sarif",
+            OutputFormat::Checkstyle => "checkstyle",
+            OutputFormat::CodeQuality => "codequality",
+        }
+    }
+
+    /// Output file used when none is given
+    pub fn default_output_path(&self) -> std::path::PathBuf {
+        match self {
+            OutputFormat::Markdown => std::path::PathBuf::from("TODO.md"),
+            OutputFormat::Json => std::path::PathBuf::from("TODO.json"),
+            OutputFormat::Html => std::path::PathBuf::from("TODO.html"),
+            OutputFormat::Csv => std::path::PathBuf::from("TODO.csv"),
+            OutputFormat::Tsv => std::path::PathBuf::from("TODO.tsv"),
+            OutputFormat::Sarif => std::path::PathBuf::from("TODO.sarif"),
+            OutputFormat::Checkstyle => std::path::PathBuf::from("TODO.checkstyle.xml"),
+            OutputFormat::CodeQuality => std::path::PathBuf::from("TODO.codequality.json"),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.trim().to_lowercase().as_str() {
+            "markdown" => Ok(OutputFormat::Markdown),
+            "json" => Ok(OutputFormat::Json),
+            "html" => Ok(OutputFormat::Html),
+            "csv" => Ok(OutputFormat::Csv),
+            "tsv" => Ok(OutputFormat::Tsv),
+            "sarif" => Ok(OutputFormat::Sarif),
+            "checkstyle" => Ok(OutputFormat::Checkstyle),
+            "codequality" => Ok(OutputFormat::CodeQuality),
+            _ => Err(format!(
+                "invalid format '{}' (expected {})",
+                name,
+                OutputFormat::VARIANTS.join(", ")
+            )),
+        }
+    }
+}
+
+/// What report formats (SARIF, Checkstyle, Code Quality) use as the rule of each task
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RuleKey {
+    /// The tag that introduced the TODO (`TODO`, `FIXME`, ...)
+    #[default]
+    Tag,
+    /// The TODO's category path (`Backend/Auth`)
+    Category,
+}
+
+impl RuleKey {
+    /// Names accepted by [`RuleKey::from_str`]
+    pub const VARIANTS: &'static [&'static str] = &["tag", "category"];
+
+    /// Name of the key, as accepted by [`RuleKey::from_str`]
+    pub fn name(&self) -> &'static str {
+        match self {
+            RuleKey::Tag => "tag",
+            RuleKey::Category => "category",
+        }
+    }
+
+    /// Rule identifier of a task
+    pub(crate) fn rule_id(&self, task: &Task) -> String {
+        match self {
+            RuleKey::Tag => task.tag.clone().unwrap_or_else(|| "TODO".to_string()),
+            RuleKey::Category => task.category.display_name(),
+        }
+    }
+}
 
-use autodomd_library_common::{Task, TaskCollection, TaskSource, TodoResult};
-use chrono::{DateTime, Utc};
+impl fmt::Display for RuleKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for RuleKey {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.trim().to_lowercase().as_str() {
+            "tag" => Ok(RuleKey::Tag),
+            "category" => Ok(RuleKey::Category),
+            _ => Err(format!(
+                "invalid rule key '{}' (expected {})",
+                name,
+                RuleKey::VARIANTS.join(" or ")
+            )),
+        }
+    }
+}
 
 /// Configuration for TODO.md generation
 #[derive(Debug, Clone)]
 pub struct GeneratorConfig {
     /// Output file path (usually "TODO.md")
     pub output_path: std::path::PathBuf,
+    /// File format to write
+    pub format: OutputFormat,
     /// Project root path for relative path calculation
     pub root_path: std::path::PathBuf,
     /// Whether to include auto-generated warning
     pub include_header: bool,
+    /// Only include code tasks with these tags (empty includes all tasks)
+    pub tag_filter: Vec<String>,
+    /// URL template for issue links, with `{id}` replaced by the issue identifier
+    pub issue_url_template: Option<String>,
+    /// URL template for source links in the HTML report, with `{path}` and `{line}` placeholders
+    pub source_url_template: Option<String>,
+    /// Leave tasks with status `done` out of the output
+    pub hide_done: bool,
+    /// Folder conventions for ordering task files and linking them to their folder's task
+    pub folder_rules: FolderRules,
+    /// Embed the dependency graph in the output as a Mermaid flowchart
+    pub include_mermaid: bool,
+    /// Also write the dependency graph to this path as Graphviz DOT
+    pub dot_output: Option<std::path::PathBuf>,
+    /// Conversion used to add up effort estimates
+    pub effort_scale: EffortScale,
+    /// Columns of the CSV/TSV export (empty for [`DEFAULT_TABLE_COLUMNS`])
+    pub columns: Vec<String>,
+    /// What report formats (SARIF, Checkstyle, Code Quality) use as the rule of each task
+    pub rule_key: RuleKey,
 }
 
 impl Default for GeneratorConfig {
     fn default() -> Self {
         Self {
             output_path: std::path::PathBuf::from("TODO.md"),
+            format: OutputFormat::Markdown,
             root_path: std::path::PathBuf::from("."),
             include_header: true,
+            tag_filter: Vec::new(),
+            issue_url_template: None,
+            source_url_template: None,
+            hide_done: false,
+            folder_rules: FolderRules::default(),
+            include_mermaid: false,
+            dot_output: None,
+            effort_scale: EffortScale::default(),
+            columns: Vec::new(),
+            rule_key: RuleKey::default(),
         }
     }
 }
 
-/// Generate TODO.md file from a task collection
-pub fn generate_todo_md(tasks: &TaskCollection, config: &GeneratorConfig) -> TodoResult<()> {
-    let mut sorted_tasks = tasks.tasks.clone();
+/// Generate TODO.md (or the configured format) from a task collection, returning the number of tasks written
+pub fn generate_todo_md(tasks: &TaskCollection, config: &GeneratorConfig) -> TodoResult<usize> {
+    let mut sorted_tasks: Vec<Task> = tasks
+        .tasks
+        .iter()
+        .filter(|task| matches_tag_filter(task, &config.tag_filter))
+        .filter(|task| !(config.hide_done && task.status == TaskStatus::Done))
+        .cloned()
+        .collect();
     sorted_tasks.sort_by(|a, b| {
         // Sort by category first, then by file path, then by line number
-        match a.category.display_name().cmp(b.category.display_name()) {
-            std::cmp::Ordering::Equal => {
-                a.location.file_path.cmp(&b.location.file_path)
-                    .then_with(|| a.location.line_number.cmp(&b.location.line_number))
-            }
+        match a.category.cmp(&b.category) {
+            std::cmp::Ordering::Equal => config
+                .folder_rules
+                .compare_paths(&a.location.file_path, &b.location.file_path)
+                .then_with(|| a.location.line_number.cmp(&b.location.line_number)),
             other => other,
         }
     });
 
-    let content = generate_markdown_content(&sorted_tasks, config);
+    let content = match config.format {
+        OutputFormat::Markdown => generate_markdown_content(&sorted_tasks, config),
+        OutputFormat::Json => generate_json_content(&sorted_tasks, config)?,
+        OutputFormat::Html => generate_html_content(&sorted_tasks, config),
+        OutputFormat::Csv => generate_table_content(&sorted_tasks, config, ',')?,
+        OutputFormat::Tsv => generate_table_content(&sorted_tasks, config, '\t')?,
+        OutputFormat::Sarif => generate_sarif_content(&sorted_tasks, config)?,
+        OutputFormat::Checkstyle => generate_checkstyle_content(&sorted_tasks, config),
+        OutputFormat::CodeQuality => generate_codequality_content(&sorted_tasks, config)?,
+    };
     fs::write(&config.output_path, content)?;
-    Ok(())
+
+    if let Some(dot_output) = &config.dot_output {
+        fs::write(
+            dot_output,
+            TaskGraph::build_with_rules(&sorted_tasks, &config.folder_rules).to_dot(),
+        )?;
+    }
+    Ok(sorted_tasks.len())
+}
+
+/// Check whether a task passes the tag filter (an empty filter matches everything)
+fn matches_tag_filter(task: &Task, tag_filter: &[String]) -> bool {
+    tag_filter.is_empty()
+        || task.tag.as_ref().is_some_and(|tag| {
+            tag_filter
+                .iter()
+                .any(|wanted| wanted.eq_ignore_ascii_case(tag))
+        })
+}
+
+/// Path of a task's file relative to the project root, with `/` separators
+fn relative_path(task: &Task, root: &std::path::Path) -> String {
+    let path = &task.location.file_path;
+    let path = path
+        .strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/");
+    path.strip_prefix("./").unwrap_or(&path).to_string()
 }
 
 /// Generate the markdown content for TODO.md
@@ -55,39 +299,43 @@ fn generate_markdown_content(tasks: &[Task], config: &GeneratorConfig) -> String
 
     // Add header
     if config.include_header {
+        let remaining = config.effort_scale.remaining(tasks);
         content.push_str("---\n");
         content.push_str("format: extramark-todo-v1\n");
         content.push_str("generator: autodomd\n");
         content.push_str("generated_at: ");
         let now: DateTime<Utc> = Utc::now();
         content.push_str(&now.format("%Y-%m-%dT%H:%M:%SZ").to_string());
-        content.push_str("\n");
+        content.push('\n');
         content.push_str("total_tasks: ");
         content.push_str(&tasks.len().to_string());
-        content.push_str("\n");
+        content.push('\n');
+        content.push_str("remaining_effort_hours: ");
+        content.push_str(&round_hours(remaining.hours).to_string());
+        content.push('\n');
         content.push_str("regenerate_command: autodomd generate\n");
         content.push_str("---\n\n");
         content.push_str("# Project Tasks\n\n");
+        if let Some(effort) = format_effort_total(&remaining, &config.effort_scale) {
+            content.push_str(&format!("Remaining effort: {}\n\n", effort));
+        }
     }
 
-    // Group tasks by category
-    let mut tasks_by_category: HashMap<String, Vec<&Task>> = HashMap::new();
-
-    for task in tasks {
-        let category_name = task.category.display_name().to_string();
-        tasks_by_category.entry(category_name).or_insert_with(Vec::new).push(task);
-    }
+    // Overdue tasks come first so broken promises are impossible to miss
+    content.push_str(&generate_overdue_section(tasks, Utc::now().date_naive()));
+    content.push_str(&generate_status_section(tasks));
 
     // Build dependency graph and show hierarchical relationships
-    let dependency_graph = build_task_dependency_graph(tasks);
-    let execution_order = topological_sort_by_dependencies(tasks, &dependency_graph);
+    let dependency_graph = TaskGraph::build_with_rules(tasks, &config.folder_rules);
 
-    // Group tasks by their foundation level (how many dependencies they have)
+    // Group tasks by their foundation level (the longest dependency chain below them)
     let mut foundation_groups: HashMap<usize, Vec<&Task>> = HashMap::new();
 
-    for task in &execution_order {
-        let dep_count = count_dependencies(task, &dependency_graph);
-        foundation_groups.entry(dep_count).or_insert_with(Vec::new).push(*task);
+    for task in dependency_graph.topological_order() {
+        foundation_groups
+            .entry(dependency_graph.level(&task.id))
+            .or_default()
+            .push(task);
     }
 
     // Generate sections showing the dependency hierarchy
@@ -103,35 +351,74 @@ fn generate_markdown_content(tasks: &[Task], config: &GeneratorConfig) -> String
                 _ => "Complex Dependencies",
             };
 
-            content.push_str(&format!("## {} (Priority Level {})\n\n", level_name, level_idx + 1));
+            content.push_str(&format!(
+                "## {} (Priority Level {})\n\n",
+                level_name,
+                level_idx + 1
+            ));
+            let level_effort = config.effort_scale.remaining(level_tasks.iter().copied());
+            if let Some(effort) = format_effort_total(&level_effort, &config.effort_scale) {
+                content.push_str(&format!("*Remaining effort: {}*\n\n", effort));
+            }
 
             for task in level_tasks.iter() {
                 // Cohesive task header with file path
-                content.push_str(&format!("### {} - {}\n", task.title, task.location.file_path.display()));
+                content.push_str(&format!(
+                    "### {} - {}\n",
+                    task.title,
+                    task.location.file_path.display()
+                ));
 
                 // Compact metadata as bullet points
-                let mut metadata_items = Vec::new();
+                let mut metadata_items = vec![format!("ID: `{}`", task.id)];
+                if let Some(tag) = &task.tag {
+                    metadata_items.push(format!("Tag: {} ({} priority)", tag, task.priority));
+                }
+                if let Some(assignee) = &task.assignee {
+                    metadata_items.push(format!("Owner: {}", assignee));
+                }
+                if task.status != TaskStatus::Open {
+                    metadata_items.push(format!("Status: {}", format_status(task)));
+                }
+                if let Some(due_date) = task.due_date {
+                    metadata_items.push(format!("Due: {}", due_date.format("%Y-%m-%d")));
+                }
+                if !task.issues.is_empty() {
+                    let issues: Vec<String> = task
+                        .issues
+                        .iter()
+                        .map(|id| format_issue_link(id, config.issue_url_template.as_deref()))
+                        .collect();
+                    metadata_items.push(format!("Issues: {}", issues.join(", ")));
+                }
 
                 // Add timestamps
                 if let Ok(metadata) = std::fs::metadata(&task.location.file_path) {
                     if let Ok(created) = metadata.created() {
                         let created_dt: DateTime<Utc> = created.into();
-                        metadata_items.push(format!("Created: {}", created_dt.format("%Y-%m-%dT%H:%M:%SZ")));
+                        metadata_items.push(format!(
+                            "Created: {}",
+                            created_dt.format("%Y-%m-%dT%H:%M:%SZ")
+                        ));
                     }
                     if let Ok(modified) = metadata.modified() {
                         let modified_dt: DateTime<Utc> = modified.into();
-                        metadata_items.push(format!("Modified: {}", modified_dt.format("%Y-%m-%dT%H:%M:%SZ")));
+                        metadata_items.push(format!(
+                            "Modified: {}",
+                            modified_dt.format("%Y-%m-%dT%H:%M:%SZ")
+                        ));
                     }
                 }
 
                 // Add effort if available
-                if let TaskSource::Markdown = task.source {
-                    if let Ok(metadata) = extract_task_metadata(&task.location.file_path) {
-                        for (key, value) in metadata {
-                            if key == "estimated_effort" {
-                                metadata_items.push(format!("Effort: {}", value));
-                            }
-                        }
+                if let Some(effort) = &task.metadata.effort {
+                    match config.effort_scale.task_hours(task) {
+                        Some(hours) => metadata_items.push(format!(
+                            "Effort: {} ({})",
+                            effort,
+                            config.effort_scale.format(hours)
+                        )),
+                        None => metadata_items.push(format!("Effort: {}", effort)),
                     }
                 }
 
@@ -140,49 +427,52 @@ fn generate_markdown_content(tasks: &[Task], config: &GeneratorConfig) -> String
                     content.push_str(&format!("- {}\n", item));
                 }
 
+                // Checklist items nested under their parent task
+                content.push_str(&generate_subtask_list(task));
+
                 // Add brief description with proper indentation
                 if let TaskSource::Markdown = task.source {
                     if let Ok(description) = extract_brief_description(&task.location.file_path) {
                         if !description.is_empty() {
                             content.push_str(&format!("-- {}\n\n", description));
                         } else {
-                            content.push_str("\n");
+                            content.push('\n');
                         }
                     }
                 }
 
                 // Show relationships in a compact format
-                if let TaskSource::Markdown = task.source {
-                    if let Ok(metadata) = extract_task_metadata(&task.location.file_path) {
-                        let mut relationships = Vec::new();
-
-                        for (key, value) in metadata {
-                            match key.as_str() {
-                                "dependencies" if !value.is_empty() && value != "[]" => {
-                                    let deps = clean_yaml_array(&value);
-                                    relationships.push(format!("Depends: {}", deps));
-                                }
-                                "blocks" if !value.is_empty() && value != "[]" => {
-                                    let blockers = clean_yaml_array(&value);
-                                    relationships.push(format!("Enables: {}", blockers));
-                                }
-                                _ => {}
-                            }
-                        }
+                let mut relationships = Vec::new();
+                if !task.metadata.dependencies.is_empty() {
+                    relationships.push(format!(
+                        "Depends: {}",
+                        task.metadata.dependencies.join(", ")
+                    ));
+                }
+                if !task.metadata.blocks.is_empty() {
+                    relationships.push(format!("Enables: {}", task.metadata.blocks.join(", ")));
+                }
 
-                        if !relationships.is_empty() {
-                            content.push_str("**Relationships:** ");
-                            content.push_str(&relationships.join(" • "));
-                            content.push_str("\n\n");
-                        }
-                    }
+                if !relationships.is_empty() {
+                    content.push_str("**Relationships:** ");
+                    content.push_str(&relationships.join(" • "));
+                    content.push_str("\n\n");
                 }
             }
 
-            content.push_str("\n");
+            content.push('\n');
         }
     }
 
+    if config.include_mermaid && !tasks.is_empty() {
+        content.push_str("## Dependency Graph\n\n```mermaid\n");
+        content.push_str(&dependency_graph.to_mermaid());
+        content.push_str("```\n\n");
+    }
+
+    content.push_str(&generate_category_section(tasks, &config.effort_scale));
+    content.push_str(&generate_owner_section(tasks));
+
     // If no tasks found, add a note
     if tasks.is_empty() {
         content.push_str("*No tasks found.*\n");
@@ -191,26 +481,196 @@ fn generate_markdown_content(tasks: &[Task], config: &GeneratorConfig) -> String
     content
 }
 
-/// Generate a simple summary of tasks by category
-pub fn generate_summary(tasks: &[Task]) -> String {
-    let mut summary = String::new();
-    let mut category_counts: HashMap<String, usize> = HashMap::new();
+/// Render a task's checklist as a completion bullet with nested items
+fn generate_subtask_list(task: &Task) -> String {
+    if task.subtasks.is_empty() {
+        return String::new();
+    }
+
+    let mut content = format!(
+        "- Subtasks: {}/{} done\n",
+        task.completed_subtasks(),
+        task.subtasks.len()
+    );
+    for subtask in &task.subtasks {
+        let mark = if subtask.done { "x" } else { " " };
+        content.push_str(&format!(
+            "  - [{}] {} (line {})\n",
+            mark, subtask.title, subtask.line_number
+        ));
+    }
+    content
+}
+
+/// Generate the "Overdue" section listing tasks past their due date
+fn generate_overdue_section(tasks: &[Task], today: NaiveDate) -> String {
+    let mut overdue: Vec<&Task> = tasks.iter().filter(|task| task.is_overdue(today)).collect();
+    if overdue.is_empty() {
+        return String::new();
+    }
+    overdue.sort_by_key(|task| task.due_date);
+
+    let mut section = format!("## Overdue ({} tasks)\n\n", overdue.len());
+    for task in overdue {
+        if let Some(due_date) = task.due_date {
+            section.push_str(&format!(
+                "- **{}** - {} (due {})\n",
+                task.title,
+                task.location,
+                due_date.format("%Y-%m-%d")
+            ));
+        }
+    }
+    section.push('\n');
 
+    section
+}
+
+/// Generate the "By Status" section, skipped while every task is still open
+fn generate_status_section(tasks: &[Task]) -> String {
+    if tasks.iter().all(|task| task.status == TaskStatus::Open) {
+        return String::new();
+    }
+
+    let mut tasks_by_status: BTreeMap<TaskStatus, Vec<&Task>> = BTreeMap::new();
     for task in tasks {
-        let category_name = task.category.display_name().to_string();
-        *category_counts.entry(category_name).or_insert(0) += 1;
+        tasks_by_status.entry(task.status).or_default().push(task);
+    }
+
+    let mut section = String::from("## By Status\n\n");
+    for (status, status_tasks) in tasks_by_status {
+        section.push_str(&format!("### {} ({} tasks)\n", status, status_tasks.len()));
+        for task in status_tasks {
+            section.push_str(&format!("- {} - {}", task.title, task.location));
+            if let (TaskStatus::Blocked, Some(reason)) = (status, &task.blocked_reason) {
+                section.push_str(&format!(" ({})", reason));
+            }
+            section.push('\n');
+        }
+        section.push('\n');
+    }
+
+    section
+}
+
+/// Format a task's status with its blocked reason, e.g. "Blocked (waiting on #42)"
+fn format_status(task: &Task) -> String {
+    match &task.blocked_reason {
+        Some(reason) if task.status == TaskStatus::Blocked => {
+            format!("{} ({})", task.status, reason)
+        }
+        _ => task.status.to_string(),
+    }
+}
+
+/// Format an issue reference, linking it when a URL template is configured
+fn format_issue_link(id: &str, url_template: Option<&str>) -> String {
+    // Plain numbers are shown GitHub-style (#123), tracker keys as-is (PROJ-45)
+    let label = if id.chars().all(|c| c.is_ascii_digit()) {
+        format!("#{}", id)
+    } else {
+        id.to_string()
+    };
+
+    match url_template {
+        Some(template) => format!("[{}]({})", label, template.replace("{id}", id)),
+        None => label,
+    }
+}
+
+/// Generate the "By Category" section with nested headings per sub-area
+fn generate_category_section(tasks: &[Task], scale: &EffortScale) -> String {
+    if tasks.is_empty() {
+        return String::new();
+    }
+
+    let mut section = String::from("## By Category\n\n");
+    CategoryTree::build(tasks).render_headings(3, scale, &mut section);
+    section
+}
+
+/// Describe a remaining-effort total, e.g. "2.5d (3 tasks without an estimate)"
+///
+/// Returns `None` when no task in the total has an estimate.
+fn format_effort_total(total: &EffortTotal, scale: &EffortScale) -> Option<String> {
+    if total.estimated == 0 {
+        return None;
     }
 
+    let mut text = scale.format(total.hours);
+    if total.unestimated > 0 {
+        text.push_str(&format!(
+            " ({} tasks without an estimate)",
+            total.unestimated
+        ));
+    }
+    Some(text)
+}
+
+/// Round hours to one decimal place for the header
+fn round_hours(hours: f64) -> f64 {
+    (hours * 10.0).round() / 10.0
+}
+
+/// Generate the "By Owner" section listing assigned tasks per owner
+fn generate_owner_section(tasks: &[Task]) -> String {
+    let mut tasks_by_owner: BTreeMap<&str, Vec<&Task>> = BTreeMap::new();
+    for task in tasks {
+        if let Some(assignee) = &task.assignee {
+            tasks_by_owner
+                .entry(assignee.as_str())
+                .or_default()
+                .push(task);
+        }
+    }
+
+    if tasks_by_owner.is_empty() {
+        return String::new();
+    }
+
+    let mut section = String::from("## By Owner\n\n");
+    for (owner, owner_tasks) in tasks_by_owner {
+        section.push_str(&format!("### {} ({} tasks)\n", owner, owner_tasks.len()));
+        for task in owner_tasks {
+            section.push_str(&format!("- {} - {}\n", task.title, task.location));
+        }
+        section.push('\n');
+    }
+
+    section
+}
+
+/// Generate a simple summary of tasks by category, with remaining effort
+pub fn generate_summary(tasks: &[Task], scale: &EffortScale) -> String {
+    let mut summary = String::new();
+
     summary.push_str("## Task Summary\n\n");
 
-    if category_counts.is_empty() {
+    if tasks.is_empty() {
         summary.push_str("*No tasks found.*\n");
     } else {
-        let mut sorted_categories: Vec<(String, usize)> = category_counts.into_iter().collect();
-        sorted_categories.sort_by(|a, b| a.0.cmp(&b.0));
+        if let Some(effort) = format_effort_total(&scale.remaining(tasks), scale) {
+            summary.push_str(&format!("Remaining effort: {}\n\n", effort));
+        }
+        // Sub-categories are indented under their parent with roll-up counts
+        CategoryTree::build(tasks).render_counts(0, scale, &mut summary);
+    }
 
-        for (category, count) in sorted_categories {
-            summary.push_str(&format!("- **{}**: {} tasks\n", category, count));
+    // Break code tasks down by the tag that introduced them
+    let mut tag_counts: HashMap<&str, usize> = HashMap::new();
+    for task in tasks {
+        if let Some(tag) = &task.tag {
+            *tag_counts.entry(tag.as_str()).or_insert(0) += 1;
+        }
+    }
+
+    if !tag_counts.is_empty() {
+        let mut sorted_tags: Vec<(&str, usize)> = tag_counts.into_iter().collect();
+        sorted_tags.sort();
+
+        summary.push_str("\n### By Tag\n\n");
+        for (tag, count) in sorted_tags {
+            summary.push_str(&format!("- **{}**: {} tasks\n", tag, count));
         }
     }
 
@@ -218,9 +678,10 @@ pub fn generate_summary(tasks: &[Task]) -> String {
 }
 
 #[cfg(test)]
+#[allow(clippy::items_after_test_module)]
 mod tests {
     use super::*;
-    use autodomd_library_common::{TaskLocation, TaskSource};
+    use autodomd_library_common::{Subtask, TaskCategory};
 
     #[test]
     fn test_generate_markdown_content_empty() {
@@ -228,7 +689,7 @@ mod tests {
         let config = GeneratorConfig::default();
         let content = generate_markdown_content(&tasks, &config);
 
-        assert!(content.contains("Auto-generated"));
+        assert!(content.contains("generator: autodomd"));
         assert!(content.contains("No tasks found"));
     }
 
@@ -245,7 +706,7 @@ mod tests {
         let config = GeneratorConfig::default();
         let content = generate_markdown_content(&tasks, &config);
 
-        assert!(content.contains("Auto-generated"));
+        assert!(content.contains("generator: autodomd"));
         assert!(content.contains("General"));
         assert!(content.contains("Test task"));
         assert!(content.contains("test.rs:42"));
@@ -262,195 +723,266 @@ mod tests {
 
         let task2 = Task::from_code(
             "Task 2".to_string(),
-            TaskCategory::Custom("Auth".to_string()),
+            TaskCategory::from_str("Auth"),
             std::path::PathBuf::from("auth.rs"),
             2,
         );
 
         let tasks = vec![task1, task2];
-        let summary = generate_summary(&tasks);
+        let summary = generate_summary(&tasks, &EffortScale::default());
 
         assert!(summary.contains("Task Summary"));
         assert!(summary.contains("General"));
         assert!(summary.contains("Auth"));
     }
-}
 
-/// Extract structured metadata from a markdown task file
-fn extract_task_metadata(file_path: &std::path::Path) -> TodoResult<Vec<(String, String)>> {
-    let content = std::fs::read_to_string(file_path)?;
-    let mut metadata = Vec::new();
-
-    // Extract YAML metadata block
-    if let Some(yaml_block) = extract_yaml_metadata_block(&content) {
-        for line in yaml_block.lines() {
-            let line = line.trim();
-            if line.is_empty() || line.starts_with('#') {
-                continue;
-            }
-            if let Some(colon_pos) = line.find(':') {
-                let key = line[..colon_pos].trim().to_string();
-                let value = line[colon_pos + 1..].trim().trim_matches('"').to_string();
-                if !key.is_empty() && !value.is_empty() {
-                    metadata.push((key, value));
-                }
-            }
-        }
-    }
+    #[test]
+    fn test_tag_filter_and_summary() {
+        let mut fixme = Task::from_code(
+            "Crash on empty input".to_string(),
+            TaskCategory::General,
+            std::path::PathBuf::from("a.rs"),
+            3,
+        );
+        fixme.tag = Some("FIXME".to_string());
+        let mut todo = Task::from_code(
+            "Tidy up".to_string(),
+            TaskCategory::General,
+            std::path::PathBuf::from("b.rs"),
+            7,
+        );
+        todo.tag = Some("TODO".to_string());
 
-    Ok(metadata)
-}
+        let filter = vec!["fixme".to_string()];
+        assert!(matches_tag_filter(&fixme, &filter));
+        assert!(!matches_tag_filter(&todo, &filter));
+        assert!(matches_tag_filter(&todo, &[]));
 
-/// Extract YAML metadata block from markdown content
-fn extract_yaml_metadata_block(content: &str) -> Option<&str> {
-    let lines: Vec<&str> = content.lines().collect();
-
-    // Look for ```yaml or ``` followed by yaml content
-    let mut in_yaml_block = false;
-    let mut yaml_start = None;
-
-    for (i, line) in lines.iter().enumerate() {
-        let trimmed = line.trim();
-        if trimmed == "```yaml" || (trimmed == "```" && i < lines.len() - 1 &&
-            (lines[i + 1].contains("priority:") || lines[i + 1].contains("dependencies:"))) {
-            in_yaml_block = true;
-            yaml_start = Some(i + 1);
-        } else if in_yaml_block && trimmed == "```" {
-            if let Some(start) = yaml_start {
-                return Some(&content[lines[start].as_ptr() as usize - content.as_ptr() as usize..
-                               lines[i].as_ptr() as usize - content.as_ptr() as usize]);
-            }
-        }
+        let summary = generate_summary(&[fixme, todo], &EffortScale::default());
+        assert!(summary.contains("By Tag"));
+        assert!(summary.contains("- **FIXME**: 1 tasks"));
     }
 
-    None
-}
+    #[test]
+    fn test_owner_section_groups_by_assignee() {
+        let mut first = Task::from_code(
+            "Write docs".to_string(),
+            TaskCategory::General,
+            std::path::PathBuf::from("a.rs"),
+            1,
+        );
+        first.assignee = Some("alice".to_string());
+        let mut second = Task::from_code(
+            "Check expiry".to_string(),
+            TaskCategory::General,
+            std::path::PathBuf::from("b.rs"),
+            2,
+        );
+        second.assignee = Some("alice".to_string());
+        let unassigned = Task::from_code(
+            "Nobody's job".to_string(),
+            TaskCategory::General,
+            std::path::PathBuf::from("c.rs"),
+            3,
+        );
 
-/// Clean YAML array format for display
-fn clean_yaml_array(value: &str) -> String {
-    // Remove brackets and quotes, clean up commas
-    value.trim_matches(&['[', ']'] as &[_])
-         .split(',')
-         .map(|s| s.trim().trim_matches('"'))
-         .filter(|s| !s.is_empty())
-         .collect::<Vec<&str>>()
-         .join(", ")
-}
+        let section = generate_owner_section(&[first, second, unassigned]);
 
-/// Build a dependency graph from folder structure and explicit metadata
-fn build_task_dependency_graph<'a>(tasks: &'a [Task]) -> HashMap<&'a str, Vec<String>> {
-    let mut graph: HashMap<&str, Vec<String>> = HashMap::new();
+        assert!(section.contains("## By Owner"));
+        assert!(section.contains("### alice (2 tasks)"));
+        assert!(section.contains("- Check expiry - b.rs:2"));
+        assert!(!section.contains("Nobody's job"));
+    }
 
-    // Create task name to task mapping
-    let task_name_map: HashMap<&str, &Task> = tasks.iter()
-        .map(|task| (task.title.as_str(), task))
-        .collect();
+    #[test]
+    fn test_format_issue_link() {
+        assert_eq!(format_issue_link("123", None), "#123");
+        assert_eq!(
+            format_issue_link("123", Some("https://tracker/{id}")),
+            "[#123](https://tracker/123)"
+        );
+        assert_eq!(
+            format_issue_link("PROJ-45", Some("https://jira/browse/{id}")),
+            "[PROJ-45](https://jira/browse/PROJ-45)"
+        );
+    }
 
-    for task in tasks {
-        let task_name = task.title.as_str();
-        let mut dependencies = Vec::new();
+    #[test]
+    fn test_overdue_section() {
+        let today = NaiveDate::from_ymd_opt(2026, 6, 1).unwrap();
+        let mut late = Task::from_code(
+            "Remove temporary hack".to_string(),
+            TaskCategory::General,
+            std::path::PathBuf::from("hack.rs"),
+            10,
+        );
+        late.due_date = NaiveDate::from_ymd_opt(2026, 5, 31);
+        let mut due_today = late.clone();
+        due_today.title = "Due today".to_string();
+        due_today.due_date = Some(today);
+        let mut finished = late.clone();
+        finished.title = "Finished late".to_string();
+        finished.status = TaskStatus::Done;
+
+        let section = generate_overdue_section(&[late, due_today, finished], today);
+
+        assert!(section.contains("## Overdue (1 tasks)"));
+        assert!(!section.contains("Finished late"));
+        assert!(section.contains("**Remove temporary hack** - hack.rs:10 (due 2026-05-31)"));
+        assert!(!section.contains("Due today"));
+        assert!(generate_overdue_section(&[], today).is_empty());
+    }
 
-        // Parse folder-based dependencies from path structure
-        if let Some(folder_deps) = parse_folder_dependencies(&task.location.file_path) {
-            dependencies.extend(folder_deps);
-        }
+    #[test]
+    fn test_status_section() {
+        let open = Task::from_code(
+            "Open task".to_string(),
+            TaskCategory::General,
+            "a.rs".into(),
+            1,
+        );
+        assert!(generate_status_section(std::slice::from_ref(&open)).is_empty());
+
+        let mut blocked = open.clone();
+        blocked.title = "Blocked task".to_string();
+        blocked.status = TaskStatus::Blocked;
+        blocked.blocked_reason = Some("waiting on #42".to_string());
+        let mut wip = open.clone();
+        wip.title = "Active task".to_string();
+        wip.status = TaskStatus::InProgress;
+
+        let section = generate_status_section(&[open, blocked.clone(), wip]);
+        let in_progress = section
+            .find("### In Progress (1 tasks)\n- Active task - a.rs:1\n")
+            .unwrap();
+        let blocked_at = section
+            .find("### Blocked (1 tasks)\n- Blocked task - a.rs:1 (waiting on #42)\n")
+            .unwrap();
+        let open_at = section
+            .find("### Open (1 tasks)\n- Open task - a.rs:1\n")
+            .unwrap();
+        assert!(in_progress < blocked_at && blocked_at < open_at);
+        assert_eq!(format_status(&blocked), "Blocked (waiting on #42)");
+    }
 
-        // Also check explicit metadata dependencies
-        if let TaskSource::Markdown = task.source {
-            if let Ok(metadata) = extract_task_metadata(&task.location.file_path) {
-                for (key, value) in metadata {
-                    if key == "dependencies" && !value.is_empty() && value != "[]" {
-                        let deps = clean_yaml_array(&value);
-                        for dep in deps.split(", ") {
-                            // Map dependency names to actual task names
-                            for (other_name, other_task) in &task_name_map {
-                                if dep.trim() == other_task.title.to_lowercase().replace(" ", "-") ||
-                                   dep.contains(&other_task.title) ||
-                                   other_task.title.to_lowercase().contains(&dep.trim().to_lowercase()) {
-                                    dependencies.push((*other_name).to_string());
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
+    #[test]
+    fn test_subtask_list() {
+        let mut task = Task::from_markdown(
+            "Login".to_string(),
+            TaskCategory::General,
+            std::path::PathBuf::from("todo/login.md"),
+        );
+        assert!(generate_subtask_list(&task).is_empty());
+
+        task.subtasks = vec![
+            Subtask {
+                title: "Design the form".to_string(),
+                done: true,
+                line_number: 3,
+            },
+            Subtask {
+                title: "Validate input".to_string(),
+                done: false,
+                line_number: 4,
+            },
+        ];
+
+        assert_eq!(
+            generate_subtask_list(&task),
+            "- Subtasks: 1/2 done\n  - [x] Design the form (line 3)\n  - [ ] Validate input (line 4)\n"
+        );
+    }
 
-        graph.insert(task_name, dependencies);
+    #[test]
+    fn test_category_tree_rolls_up_counts() {
+        let task = |title: &str, category: &str, line: usize| {
+            Task::from_code(
+                title.to_string(),
+                TaskCategory::from_str(category),
+                std::path::PathBuf::from("svc.rs"),
+                line,
+            )
+        };
+        let tasks = vec![
+            task("Rotate keys", "Backend/Auth", 1),
+            task("Expire sessions", "Backend/Auth", 2),
+            task("Add indexes", "Backend/Storage", 3),
+            task("Tidy logs", "Backend", 4),
+            task("Fix padding", "Frontend", 5),
+        ];
+
+        let section = generate_category_section(&tasks, &EffortScale::default());
+        assert!(section.contains("### Backend (4 tasks)\n- Tidy logs - svc.rs:4\n"));
+        assert!(section.contains(
+            "#### Auth (2 tasks)\n- Rotate keys - svc.rs:1\n- Expire sessions - svc.rs:2\n"
+        ));
+        assert!(section.contains("#### Storage (1 tasks)"));
+        assert!(section.contains("### Frontend (1 tasks)"));
+
+        let summary = generate_summary(&tasks, &EffortScale::default());
+        assert!(summary
+            .contains("- **Backend**: 4 tasks\n  - **Auth**: 2 tasks\n  - **Storage**: 1 tasks\n"));
     }
 
-    graph
-}
+    #[test]
+    fn test_effort_totals() {
+        let task = |title: &str, category: &str, effort: Option<&str>, status: TaskStatus| {
+            let mut task = Task::from_markdown(
+                title.to_string(),
+                TaskCategory::from_str(category),
+                std::path::PathBuf::from(format!("todo/{}.md", title)),
+            );
+            task.id = title.to_string();
+            task.metadata.effort = effort.map(str::to_string);
+            task.status = status;
+            task
+        };
+        let tasks = vec![
+            task("keys", "Backend/Auth", Some("3d"), TaskStatus::Open),
+            task("sessions", "Backend/Auth", Some("m"), TaskStatus::Open),
+            task("indexes", "Backend", None, TaskStatus::Open),
+            task("shipped", "Backend", Some("2w"), TaskStatus::Done),
+        ];
+        let scale = EffortScale::default();
+
+        let summary = generate_summary(&tasks, &scale);
+        assert!(summary.contains("Remaining effort: 4.5d (1 tasks without an estimate)\n"));
+        assert!(summary.contains(
+            "- **Backend**: 4 tasks, 4.5d remaining\n  - **Auth**: 2 tasks, 4.5d remaining\n"
+        ));
+
+        let content = generate_markdown_content(&tasks, &GeneratorConfig::default());
+        assert!(content.contains("remaining_effort_hours: 36\n"));
+        assert!(content.contains("- Effort: m (1.5d)\n"));
+        assert!(content.contains("*Remaining effort: 4.5d (1 tasks without an estimate)*"));
+        assert!(content.contains("### Backend (4 tasks, 4.5d remaining)\n"));
+    }
 
-/// Parse folder structure to determine dependencies
-fn parse_folder_dependencies(file_path: &std::path::Path) -> Option<Vec<String>> {
-    let path_str = file_path.to_string_lossy();
-
-    // Extract the relative path from todo/
-    if let Some(todo_pos) = path_str.find("todo/") {
-        let relative_path = &path_str[todo_pos + 5..]; // Skip "todo/"
-        let parts: Vec<&str> = relative_path.split('/').collect();
-
-        if parts.len() > 1 {
-            // This file is in a subfolder, so it depends on the parent folder's task
-            // Map folder numbers to task titles - everything depends on core scanning
-            let parent_task = match parts[0] {
-                "060-git-integration" => "Implement Git Integration and Automation",
-                "070-advanced-features" => "Implement Core Auto-Todo Functionality", // Advanced features depend on core
-                "080-documentation" => "Implement Core Auto-Todo Functionality", // Documentation depends on core
-                _ => "Implement Core Auto-Todo Functionality", // Default to core dependency
-            };
-            Some(vec![parent_task.to_string()])
-        } else {
-            // Root level file, no dependencies
-            None
+    #[test]
+    fn test_output_format_names() {
+        for name in OutputFormat::VARIANTS {
+            assert_eq!(name.parse::<OutputFormat>().unwrap().to_string(), *name);
         }
-    } else {
-        None
+        assert_eq!("JSON".parse::<OutputFormat>(), Ok(OutputFormat::Json));
+        assert!("yaml".parse::<OutputFormat>().is_err());
     }
-}
-
-/// Count dependencies for a task
-fn count_dependencies<'a>(task: &'a Task, graph: &HashMap<&'a str, Vec<String>>) -> usize {
-    graph.get(task.title.as_str()).map(|deps| deps.len()).unwrap_or(0)
-}
-
-/// Topological sort tasks by dependencies (simple implementation)
-fn topological_sort_by_dependencies<'a>(tasks: &'a [Task], graph: &HashMap<&'a str, Vec<String>>) -> Vec<&'a Task> {
-    let mut result = Vec::new();
-    let mut processed = std::collections::HashSet::new();
-
-    // Simple topological sort - tasks with fewer dependencies first
-    let mut task_refs: Vec<&Task> = tasks.iter().collect();
-    task_refs.sort_by_key(|task| count_dependencies(task, graph));
 
-    for &task in &task_refs {
-        if !processed.contains(&task.title.as_str()) {
-            result.push(task);
-            processed.insert(task.title.as_str());
+    #[test]
+    fn test_rule_key_names() {
+        for name in RuleKey::VARIANTS {
+            assert_eq!(name.parse::<RuleKey>().unwrap().to_string(), *name);
         }
+        assert_eq!("Category".parse::<RuleKey>(), Ok(RuleKey::Category));
+        assert!("owner".parse::<RuleKey>().is_err());
     }
-
-    result
 }
 
 /// Extract a brief description from a markdown task file
 fn extract_brief_description(file_path: &std::path::Path) -> TodoResult<String> {
     let content = std::fs::read_to_string(file_path)?;
 
-    // Skip YAML metadata block if present
-    let content_after_yaml = if let Some(yaml_block) = extract_yaml_metadata_block(&content) {
-        // Find the end of YAML block and skip to content after ```
-        if let Some(end_pos) = content[yaml_block.as_ptr() as usize - content.as_ptr() as usize..].find("```\n") {
-            let yaml_end_pos = yaml_block.as_ptr() as usize - content.as_ptr() as usize + end_pos + 4;
-            &content[yaml_end_pos..]
-        } else {
-            &content
-        }
-    } else {
-        &content
-    };
+    // Skip the metadata block if present
+    let content_after_yaml = parse_frontmatter(&content).map(|frontmatter| frontmatter.body)?;
 
     // Look for Overview section
     if let Some(overview_start) = content_after_yaml.find("## Overview") {
@@ -464,7 +996,7 @@ fn extract_brief_description(file_path: &std::path::Path) -> TodoResult<String>
                     let remaining = &overview_text[i + 1..];
                     let next_char = remaining.chars().next();
 
-                    if next_char.map_or(true, |c| c.is_whitespace() || c == '\n') {
+                    if next_char.map(|c| c.is_whitespace()).unwrap_or(true) {
                         // This looks like a sentence-ending period
                         if i < 300 {
                             return Ok(overview_text[..i + 1].to_string());
@@ -476,11 +1008,17 @@ fn extract_brief_description(file_path: &std::path::Path) -> TodoResult<String>
             }
 
             // No proper sentence end found, or sentence too long - truncate at 300 chars
-            Ok(overview_text.chars().take(300).collect::<String>() + if overview_text.len() > 300 { "..." } else { "" })
+            Ok(overview_text.chars().take(300).collect::<String>()
+                + if overview_text.len() > 300 { "..." } else { "" })
         } else {
             // Take the whole overview section if no other sections follow
             // Take up to 300 chars for the whole overview section
-            Ok(after_overview.trim().chars().take(300).collect::<String>() + if after_overview.len() > 300 { "..." } else { "" })
+            Ok(after_overview.trim().chars().take(300).collect::<String>()
+                + if after_overview.len() > 300 {
+                    "..."
+                } else {
+                    ""
+                })
         }
     } else {
         Ok("".to_string())
@@ -0,0 +1,108 @@
+//! Category tree used to render tasks by area and sub-area
+//!
+//! Each node holds the tasks filed directly under it plus its child
+//! categories, so counts can be rolled up per subtree.
+
+use std::collections::BTreeMap;
+
+use autodomd_library_common::Task;
+use autodomd_library_metadata::{EffortScale, EffortTotal};
+
+/// Deepest markdown heading level; deeper categories reuse it
+const MAX_HEADING_LEVEL: usize = 6;
+
+/// A category and everything nested below it
+#[derive(Debug, Default)]
+pub(crate) struct CategoryTree<'a> {
+    /// Tasks whose category ends at this node
+    tasks: Vec<&'a Task>,
+    /// Child categories by name
+    children: BTreeMap<&'a str, CategoryTree<'a>>,
+}
+
+impl<'a> CategoryTree<'a> {
+    /// Build the tree from a list of tasks
+    pub(crate) fn build(tasks: &'a [Task]) -> Self {
+        let mut root = Self::default();
+        for task in tasks {
+            let node = task
+                .category
+                .segments()
+                .into_iter()
+                .fold(&mut root, |node, segment| {
+                    node.children.entry(segment).or_default()
+                });
+            node.tasks.push(task);
+        }
+        root
+    }
+
+    /// Number of tasks in this node and all of its descendants
+    pub(crate) fn total(&self) -> usize {
+        self.tasks.len()
+            + self
+                .children
+                .values()
+                .map(CategoryTree::total)
+                .sum::<usize>()
+    }
+
+    /// Remaining effort of this node and all of its descendants
+    pub(crate) fn remaining_effort(&self, scale: &EffortScale) -> EffortTotal {
+        self.children.values().fold(
+            scale.remaining(self.tasks.iter().copied()),
+            |mut total, child| {
+                let child_total = child.remaining_effort(scale);
+                total.hours += child_total.hours;
+                total.estimated += child_total.estimated;
+                total.unestimated += child_total.unestimated;
+                total
+            },
+        )
+    }
+
+    /// Task count with remaining effort when any task is estimated, e.g. "3 tasks, 2.5d remaining"
+    fn describe(&self, scale: &EffortScale) -> String {
+        let effort = self.remaining_effort(scale);
+        if effort.estimated == 0 {
+            format!("{} tasks", self.total())
+        } else {
+            format!(
+                "{} tasks, {} remaining",
+                self.total(),
+                scale.format(effort.hours)
+            )
+        }
+    }
+
+    /// Render the children of this node as nested headings with roll-up counts
+    pub(crate) fn render_headings(&self, level: usize, scale: &EffortScale, output: &mut String) {
+        for (name, child) in &self.children {
+            let hashes = "#".repeat(level.min(MAX_HEADING_LEVEL));
+            output.push_str(&format!(
+                "{} {} ({})\n",
+                hashes,
+                name,
+                child.describe(scale)
+            ));
+            for task in &child.tasks {
+                output.push_str(&format!("- {} - {}\n", task.title, task.location));
+            }
+            output.push('\n');
+            child.render_headings(level + 1, scale, output);
+        }
+    }
+
+    /// Render the children of this node as an indented list of roll-up counts
+    pub(crate) fn render_counts(&self, depth: usize, scale: &EffortScale, output: &mut String) {
+        for (name, child) in &self.children {
+            output.push_str(&format!(
+                "{}- **{}**: {}\n",
+                "  ".repeat(depth),
+                name,
+                child.describe(scale)
+            ));
+            child.render_counts(depth + 1, scale, output);
+        }
+    }
+}
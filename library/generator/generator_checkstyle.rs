@@ -0,0 +1,109 @@
+//! Checkstyle XML output for CI warning collectors
+//!
+//! Code TODOs are reported as `<error>` entries grouped by `<file>`, the
+//! format read by the Jenkins warnings plugin and most CI dashboards. The
+//! `source` attribute names the rule chosen by [`RuleKey`](crate::RuleKey) as
+//! `autodomd.FIXME`. Markdown tasks are left out.
+
+use std::collections::BTreeMap;
+
+use autodomd_library_common::{Task, TaskPriority, TaskSource};
+
+use crate::generator_html::escape;
+use crate::{relative_path, GeneratorConfig};
+
+/// Checkstyle format version written to the report
+const CHECKSTYLE_VERSION: &str = "4.3";
+
+/// Render the code tasks as a Checkstyle XML report
+pub(crate) fn generate_checkstyle_content(tasks: &[Task], config: &GeneratorConfig) -> String {
+    let mut files: BTreeMap<String, Vec<&Task>> = BTreeMap::new();
+    for task in tasks.iter().filter(|task| task.source == TaskSource::Code) {
+        files
+            .entry(relative_path(task, &config.root_path))
+            .or_default()
+            .push(task);
+    }
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<checkstyle version=\"{}\">\n",
+        CHECKSTYLE_VERSION
+    ));
+    for (path, tasks) in files {
+        xml.push_str(&format!("  <file name=\"{}\">\n", escape(&path)));
+        for task in tasks {
+            xml.push_str("    <error");
+            if let Some(line) = task.location.line_number {
+                xml.push_str(&format!(" line=\"{}\"", line));
+            }
+            if let Some(column) = task.location.column_number {
+                xml.push_str(&format!(" column=\"{}\"", column));
+            }
+            xml.push_str(&format!(
+                " severity=\"{}\" message=\"{}\" source=\"autodomd.{}\"/>\n",
+                severity(task.priority),
+                escape(&task.title),
+                escape(&config.rule_key.rule_id(task))
+            ));
+        }
+        xml.push_str("  </file>\n");
+    }
+    xml.push_str("</checkstyle>\n");
+    xml
+}
+
+/// Checkstyle severity for a task priority
+fn severity(priority: TaskPriority) -> &'static str {
+    match priority {
+        TaskPriority::High => "error",
+        TaskPriority::Medium => "warning",
+        TaskPriority::Low => "info",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use autodomd_library_common::{TaskCategory, TaskLocation};
+
+    #[test]
+    fn test_checkstyle_report() {
+        let mut fixme = Task::new(
+            "Handle <empty> & \"blank\" input".to_string(),
+            TaskCategory::General,
+            TaskPriority::High,
+            TaskLocation::from_source_file("./src/b.rs".into(), 3).with_column(8),
+            TaskSource::Code,
+        );
+        fixme.tag = Some("FIXME".to_string());
+        let mut todo = Task::from_code(
+            "Cache this".to_string(),
+            TaskCategory::General,
+            "src/a.rs".into(),
+            9,
+        );
+        todo.tag = Some("TODO".to_string());
+        let markdown = Task::from_markdown(
+            "Plan".to_string(),
+            TaskCategory::General,
+            "todo/plan.md".into(),
+        );
+
+        let xml =
+            generate_checkstyle_content(&[fixme, todo, markdown], &GeneratorConfig::default());
+
+        assert_eq!(
+            xml,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <checkstyle version=\"4.3\">\n  \
+             <file name=\"src/a.rs\">\n    \
+             <error line=\"9\" severity=\"warning\" message=\"Cache this\" source=\"autodomd.TODO\"/>\n  \
+             </file>\n  \
+             <file name=\"src/b.rs\">\n    \
+             <error line=\"3\" column=\"8\" severity=\"error\" message=\"Handle &lt;empty&gt; &amp; &quot;blank&quot; input\" source=\"autodomd.FIXME\"/>\n  \
+             </file>\n\
+             </checkstyle>\n"
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! GitLab Code Quality output for merge-request widgets
+//!
+//! The report is a JSON array with one issue per code TODO. GitLab compares
+//! reports between branches by `fingerprint`, so it is derived from the
+//! unique task ID (tag, file and description) rather than the line: moving a
+//! TODO keeps its fingerprint. Markdown tasks are left out.
+
+use autodomd_library_common::{Task, TaskPriority, TaskSource, TodoError, TodoResult};
+use serde::Serialize;
+
+use crate::{relative_path, GeneratorConfig};
+
+#[derive(Serialize)]
+struct CodeQualityIssue {
+    description: String,
+    check_name: String,
+    fingerprint: String,
+    severity: &'static str,
+    location: CodeQualityLocation,
+}
+
+#[derive(Serialize)]
+struct CodeQualityLocation {
+    path: String,
+    lines: CodeQualityLines,
+}
+
+#[derive(Serialize)]
+struct CodeQualityLines {
+    begin: usize,
+}
+
+/// Render the code tasks as a pretty-printed GitLab Code Quality report
+pub(crate) fn generate_codequality_content(
+    tasks: &[Task],
+    config: &GeneratorConfig,
+) -> TodoResult<String> {
+    let issues: Vec<CodeQualityIssue> = tasks
+        .iter()
+        .filter(|task| task.source == TaskSource::Code)
+        .map(|task| CodeQualityIssue {
+            description: task.title.clone(),
+            check_name: config.rule_key.rule_id(task),
+            fingerprint: fingerprint(task),
+            severity: severity(task.priority),
+            location: CodeQualityLocation {
+                path: relative_path(task, &config.root_path),
+                lines: CodeQualityLines {
+                    begin: task.location.line_number.unwrap_or(1),
+                },
+            },
+        })
+        .collect();
+
+    let mut json = serde_json::to_string_pretty(&issues).map_err(|e| {
+        TodoError::InvalidFormat(format!(
+            "cannot write tasks as a Code Quality report: {}",
+            e
+        ))
+    })?;
+    json.push('\n');
+    Ok(json)
+}
+
+/// Stable fingerprint of a task, from its unique ID
+fn fingerprint(task: &Task) -> String {
+    format!("{:x}", md5::compute(format!("autodomd\0{}", task.id)))
+}
+
+/// Code Quality severity for a task priority
+fn severity(priority: TaskPriority) -> &'static str {
+    match priority {
+        TaskPriority::High => "major",
+        TaskPriority::Medium => "minor",
+        TaskPriority::Low => "info",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use autodomd_library_common::TaskCategory;
+
+    fn fixme(line: usize) -> Task {
+        let mut task = Task::from_code(
+            "Handle empty input".to_string(),
+            TaskCategory::General,
+            "./src/a.rs".into(),
+            line,
+        );
+        task.id = "fixme-1234abcd".to_string();
+        task.tag = Some("FIXME".to_string());
+        task.priority = TaskPriority::High;
+        task
+    }
+
+    #[test]
+    fn test_codequality_report() {
+        let markdown = Task::from_markdown(
+            "Plan".to_string(),
+            TaskCategory::General,
+            "todo/plan.md".into(),
+        );
+        // A repeated TODO gets a suffixed ID from `ensure_unique_ids`
+        let mut repeated = fixme(10);
+        repeated.id.push_str("-2");
+        let json = generate_codequality_content(
+            &[fixme(3), repeated, markdown],
+            &GeneratorConfig::default(),
+        )
+        .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value.as_array().unwrap().len(), 2);
+        assert_eq!(value[0]["description"], "Handle empty input");
+        assert_eq!(value[0]["check_name"], "FIXME");
+        assert_eq!(value[0]["severity"], "major");
+        assert_eq!(value[0]["location"]["path"], "src/a.rs");
+        assert_eq!(value[0]["location"]["lines"]["begin"], 3);
+        assert_ne!(value[0]["fingerprint"], value[1]["fingerprint"]);
+
+        // Moving the TODO to another line keeps its fingerprint
+        let moved =
+            generate_codequality_content(&[fixme(42)], &GeneratorConfig::default()).unwrap();
+        let moved: serde_json::Value = serde_json::from_str(&moved).unwrap();
+        assert_eq!(moved[0]["fingerprint"], value[0]["fingerprint"]);
+    }
+}
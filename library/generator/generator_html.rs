@@ -0,0 +1,395 @@
+//! Self-contained HTML report
+//!
+//! One file with its CSS and JavaScript embedded, so it can be published as a
+//! CI artifact and opened without a server. Each task is a card carrying its
+//! category, priority, source and status as `data-` attributes; the script
+//! only hides and shows cards, so the report reads fine with scripts disabled.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use autodomd_library_common::{Task, TaskPriority, TaskSource, TaskStatus};
+use autodomd_library_metadata::parse_frontmatter;
+use chrono::Utc;
+
+use crate::GeneratorConfig;
+
+const STYLE: &str = r#"
+body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; margin: 0; background: #f6f8fa; color: #1f2328; }
+header { background: #24292f; color: #fff; padding: 1rem 2rem; }
+header h1 { margin: 0 0 .25rem; font-size: 1.5rem; }
+header p { margin: 0; color: #c9d1d9; font-size: .9rem; }
+#filters { display: flex; flex-wrap: wrap; gap: .5rem; padding: 1rem 2rem; background: #fff; border-bottom: 1px solid #d0d7de; position: sticky; top: 0; }
+#filters input, #filters select { padding: .35rem .5rem; border: 1px solid #d0d7de; border-radius: 6px; font-size: .9rem; }
+#filters input { flex: 1; min-width: 12rem; }
+#count { align-self: center; color: #57606a; font-size: .9rem; }
+main { padding: 1rem 2rem; max-width: 70rem; }
+.task { background: #fff; border: 1px solid #d0d7de; border-left: 4px solid #d0d7de; border-radius: 6px; padding: .75rem 1rem; margin-bottom: .75rem; }
+.task[data-priority="high"] { border-left-color: #cf222e; }
+.task[data-priority="medium"] { border-left-color: #bf8700; }
+.task[data-priority="low"] { border-left-color: #1a7f37; }
+.task[data-status="done"] { opacity: .6; }
+.task h2 { font-size: 1.05rem; margin: 0 0 .35rem; }
+.task[data-status="done"] h2 { text-decoration: line-through; }
+.meta { display: flex; flex-wrap: wrap; gap: .35rem; margin: .35rem 0; font-size: .8rem; }
+.chip { background: #eaeef2; border-radius: 2em; padding: .1rem .6rem; }
+.chip.status-blocked { background: #ffebe9; color: #cf222e; }
+.chip.status-in-progress { background: #ddf4ff; color: #0969da; }
+.location { font-family: ui-monospace, SFMono-Regular, Menlo, monospace; font-size: .85rem; }
+.subtasks { margin: .35rem 0; padding-left: 1.25rem; font-size: .9rem; }
+details { margin-top: .35rem; }
+details pre { white-space: pre-wrap; background: #f6f8fa; padding: .5rem; border-radius: 6px; font-size: .85rem; }
+.empty { color: #57606a; }
+"#;
+
+const SCRIPT: &str = r#"
+(function () {
+  var search = document.getElementById('search');
+  var selects = Array.prototype.slice.call(document.querySelectorAll('#filters select'));
+  var tasks = Array.prototype.slice.call(document.querySelectorAll('.task'));
+  var count = document.getElementById('count');
+
+  function matches(task, select) {
+    var wanted = select.value;
+    if (!wanted) return true;
+    var actual = task.getAttribute('data-' + select.name) || '';
+    // Categories match their sub-categories too
+    return actual === wanted || (select.name === 'category' && actual.indexOf(wanted + '/') === 0);
+  }
+
+  function update() {
+    var text = search.value.trim().toLowerCase();
+    var shown = 0;
+    tasks.forEach(function (task) {
+      var visible = selects.every(function (select) { return matches(task, select); })
+        && (!text || task.textContent.toLowerCase().indexOf(text) !== -1);
+      task.hidden = !visible;
+      if (visible) shown++;
+    });
+    count.textContent = shown + ' of ' + tasks.length + ' tasks';
+  }
+
+  search.addEventListener('input', update);
+  selects.forEach(function (select) { select.addEventListener('change', update); });
+  update();
+})();
+"#;
+
+/// Render the tasks as a single HTML page
+pub(crate) fn generate_html_content(tasks: &[Task], config: &GeneratorConfig) -> String {
+    let mut html =
+        String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    html.push_str("<meta name=\"generator\" content=\"autodomd\">\n");
+    html.push_str("<title>Project Tasks</title>\n");
+    html.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", STYLE));
+
+    html.push_str("<header>\n<h1>Project Tasks</h1>\n");
+    html.push_str(&format!(
+        "<p>{} tasks &middot; generated by autodomd at {}</p>\n</header>\n",
+        tasks.len(),
+        Utc::now().format("%Y-%m-%dT%H:%M:%SZ")
+    ));
+
+    html.push_str(&filters(tasks));
+
+    html.push_str("<main>\n");
+    if tasks.is_empty() {
+        html.push_str("<p class=\"empty\">No tasks found.</p>\n");
+    }
+    for task in tasks {
+        html.push_str(&task_card(task, config));
+    }
+    html.push_str("</main>\n");
+
+    html.push_str(&format!("<script>{}</script>\n</body>\n</html>\n", SCRIPT));
+    html
+}
+
+/// The search box and one drop-down per filterable field
+fn filters(tasks: &[Task]) -> String {
+    // Every category and each of its parents, so a parent selects its whole subtree
+    let categories: BTreeSet<String> = tasks
+        .iter()
+        .flat_map(|task| {
+            let segments = task.category.segments();
+            (1..=segments.len()).map(move |depth| segments[..depth].join("/"))
+        })
+        .collect();
+    let categories: Vec<(String, String)> = categories
+        .into_iter()
+        .map(|name| (name.clone(), name))
+        .collect();
+
+    let priorities: Vec<(String, String)> =
+        [TaskPriority::High, TaskPriority::Medium, TaskPriority::Low]
+            .iter()
+            .map(|priority| {
+                (
+                    priority_value(*priority).to_string(),
+                    priority.display_name().to_string(),
+                )
+            })
+            .collect();
+    let sources = vec![
+        ("markdown".to_string(), "Task file".to_string()),
+        ("code".to_string(), "Code comment".to_string()),
+    ];
+    let statuses: Vec<(String, String)> = [
+        TaskStatus::Open,
+        TaskStatus::InProgress,
+        TaskStatus::Blocked,
+        TaskStatus::Done,
+    ]
+    .iter()
+    .map(|status| {
+        (
+            status_value(*status).to_string(),
+            status.display_name().to_string(),
+        )
+    })
+    .collect();
+
+    let mut html = String::from("<nav id=\"filters\">\n");
+    html.push_str("<input id=\"search\" type=\"search\" placeholder=\"Search tasks\" aria-label=\"Search tasks\">\n");
+    html.push_str(&select("category", "All categories", &categories));
+    html.push_str(&select("priority", "All priorities", &priorities));
+    html.push_str(&select("source", "All sources", &sources));
+    html.push_str(&select("status", "All statuses", &statuses));
+    html.push_str("<span id=\"count\"></span>\n</nav>\n");
+    html
+}
+
+fn select(name: &str, label: &str, options: &[(String, String)]) -> String {
+    let mut html = format!(
+        "<select name=\"{}\" aria-label=\"{}\">\n<option value=\"\">{}</option>\n",
+        name, label, label
+    );
+    for (value, text) in options {
+        html.push_str(&format!(
+            "<option value=\"{}\">{}</option>\n",
+            escape(value),
+            escape(text)
+        ));
+    }
+    html.push_str("</select>\n");
+    html
+}
+
+/// One task as an `<article>` with its metadata, subtasks and description
+fn task_card(task: &Task, config: &GeneratorConfig) -> String {
+    let mut html = format!(
+        "<article class=\"task\" id=\"{}\" data-category=\"{}\" data-priority=\"{}\" data-source=\"{}\" data-status=\"{}\">\n",
+        escape(&task.id),
+        escape(&task.category.display_name()),
+        priority_value(task.priority),
+        source_value(&task.source),
+        status_value(task.status)
+    );
+    html.push_str(&format!("<h2>{}</h2>\n", escape(&task.title)));
+    html.push_str(&format!(
+        "<a class=\"location\" href=\"{}\">{}</a>\n",
+        escape(&source_link(task, config.source_url_template.as_deref())),
+        escape(&task.location.to_string())
+    ));
+
+    let mut chips = vec![
+        format!("<span class=\"chip\">{}</span>", escape(&task.id)),
+        format!(
+            "<span class=\"chip\">{}</span>",
+            escape(&task.category.display_name())
+        ),
+        format!("<span class=\"chip\">{} priority</span>", task.priority),
+    ];
+    if task.status != TaskStatus::Open {
+        let mut status = task.status.to_string();
+        if let (TaskStatus::Blocked, Some(reason)) = (task.status, &task.blocked_reason) {
+            status.push_str(&format!(": {}", reason));
+        }
+        chips.push(format!(
+            "<span class=\"chip status-{}\">{}</span>",
+            status_value(task.status),
+            escape(&status)
+        ));
+    }
+    if let Some(tag) = &task.tag {
+        chips.push(format!("<span class=\"chip\">{}</span>", escape(tag)));
+    }
+    if let Some(assignee) = &task.assignee {
+        chips.push(format!("<span class=\"chip\">@{}</span>", escape(assignee)));
+    }
+    if let Some(due_date) = task.due_date {
+        chips.push(format!(
+            "<span class=\"chip\">due {}</span>",
+            due_date.format("%Y-%m-%d")
+        ));
+    }
+    if let Some(effort) = &task.metadata.effort {
+        chips.push(format!(
+            "<span class=\"chip\">effort {}</span>",
+            escape(effort)
+        ));
+    }
+    for issue in &task.issues {
+        chips.push(match &config.issue_url_template {
+            Some(template) => format!(
+                "<a class=\"chip\" href=\"{}\">{}</a>",
+                escape(&template.replace("{id}", issue)),
+                escape(issue)
+            ),
+            None => format!("<span class=\"chip\">{}</span>", escape(issue)),
+        });
+    }
+    html.push_str(&format!("<div class=\"meta\">{}</div>\n", chips.join(" ")));
+
+    if !task.metadata.dependencies.is_empty() {
+        let links: Vec<String> = task
+            .metadata
+            .dependencies
+            .iter()
+            .map(|id| format!("<a href=\"#{}\">{}</a>", escape(id), escape(id)))
+            .collect();
+        html.push_str(&format!(
+            "<div class=\"meta\">Depends on: {}</div>\n",
+            links.join(", ")
+        ));
+    }
+
+    if !task.subtasks.is_empty() {
+        html.push_str(&format!(
+            "<div class=\"meta\">Subtasks: {}/{} done</div>\n<ul class=\"subtasks\">\n",
+            task.completed_subtasks(),
+            task.subtasks.len()
+        ));
+        for subtask in &task.subtasks {
+            let checked = if subtask.done { " checked" } else { "" };
+            html.push_str(&format!(
+                "<li><input type=\"checkbox\" disabled{}> {}</li>\n",
+                checked,
+                escape(&subtask.title)
+            ));
+        }
+        html.push_str("</ul>\n");
+    }
+
+    if task.source == TaskSource::Markdown {
+        if let Some(description) = read_description(&task.location.file_path) {
+            html.push_str(&format!(
+                "<details>\n<summary>Description</summary>\n<pre>{}</pre>\n</details>\n",
+                escape(&description)
+            ));
+        }
+    }
+
+    html.push_str("</article>\n");
+    html
+}
+
+/// Link to a task's source: the URL template when configured, else the relative path
+///
+/// The template may use `{path}` and `{line}`, e.g.
+/// `https://github.com/owner/repo/blob/main/{path}#L{line}`.
+fn source_link(task: &Task, url_template: Option<&str>) -> String {
+    let path = task.location.file_path.to_string_lossy();
+    let path = path.strip_prefix("./").unwrap_or(&path).replace('\\', "/");
+    let line = task.location.line_number.unwrap_or(1);
+
+    match url_template {
+        Some(template) => template
+            .replace("{path}", &path)
+            .replace("{line}", &line.to_string()),
+        None if task.location.line_number.is_some() => format!("{}#L{}", path, line),
+        None => path,
+    }
+}
+
+/// Body of a task file without its metadata block, if it has any text
+fn read_description(path: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let body = parse_frontmatter(&content).ok()?.body;
+    let body = body.trim();
+    (!body.is_empty()).then(|| body.to_string())
+}
+
+fn priority_value(priority: TaskPriority) -> &'static str {
+    match priority {
+        TaskPriority::High => "high",
+        TaskPriority::Medium => "medium",
+        TaskPriority::Low => "low",
+    }
+}
+
+fn source_value(source: &TaskSource) -> &'static str {
+    match source {
+        TaskSource::Markdown => "markdown",
+        TaskSource::Code => "code",
+    }
+}
+
+fn status_value(status: TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Open => "open",
+        TaskStatus::InProgress => "in-progress",
+        TaskStatus::Blocked => "blocked",
+        TaskStatus::Done => "done",
+    }
+}
+
+/// Escape text for HTML content and quoted attributes
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use autodomd_library_common::TaskCategory;
+
+    #[test]
+    fn test_html_report() {
+        let mut fixme = Task::from_code(
+            "Handle <empty> input".to_string(),
+            TaskCategory::from_str("Backend/Auth"),
+            "./src/auth.rs".into(),
+            12,
+        );
+        fixme.id = "fixme-1234abcd".to_string();
+        fixme.priority = TaskPriority::High;
+        fixme.status = TaskStatus::Blocked;
+        fixme.blocked_reason = Some("waiting on #42".to_string());
+
+        let html = generate_html_content(&[fixme], &GeneratorConfig::default());
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<style>") && html.contains("<script>"));
+        assert!(html.contains(
+            "data-category=\"Backend/Auth\" data-priority=\"high\" data-source=\"code\" data-status=\"blocked\""
+        ));
+        assert!(html.contains("<option value=\"Backend\">Backend</option>"));
+        assert!(html.contains("<option value=\"Backend/Auth\">Backend/Auth</option>"));
+        assert!(html.contains("<h2>Handle &lt;empty&gt; input</h2>"));
+        assert!(html.contains("<a class=\"location\" href=\"src/auth.rs#L12\">"));
+        assert!(html.contains("Blocked: waiting on #42"));
+    }
+
+    #[test]
+    fn test_source_link() {
+        let task = Task::from_code(
+            "x".to_string(),
+            TaskCategory::General,
+            "./src/lib.rs".into(),
+            7,
+        );
+        let template = "https://github.com/owner/repo/blob/main/{path}#L{line}";
+
+        assert_eq!(source_link(&task, None), "src/lib.rs#L7");
+        assert_eq!(
+            source_link(&task, Some(template)),
+            "https://github.com/owner/repo/blob/main/src/lib.rs#L7"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! JSON output for tools that consume the task list
+//!
+//! The document is an object with a `schema_version`, the tasks exactly as
+//! scanned (location, category, priority, source, status, metadata, ...) and
+//! the dependency edges of the graph, including those implied by folder rules.
+//! Bump [`JSON_SCHEMA_VERSION`] whenever a field is renamed or removed.
+
+use autodomd_library_common::{Task, TodoError, TodoResult};
+use autodomd_library_graph::TaskGraph;
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::GeneratorConfig;
+
+/// Version of the JSON document layout
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct JsonDocument<'a> {
+    schema_version: u32,
+    generator: &'static str,
+    generated_at: String,
+    tasks: &'a [Task],
+    edges: Vec<JsonEdge<'a>>,
+}
+
+/// `task` cannot start before `depends_on` is done
+#[derive(Serialize)]
+struct JsonEdge<'a> {
+    task: &'a str,
+    depends_on: &'a str,
+}
+
+/// Render the tasks and their dependency edges as a pretty-printed JSON document
+pub(crate) fn generate_json_content(
+    tasks: &[Task],
+    config: &GeneratorConfig,
+) -> TodoResult<String> {
+    let graph = TaskGraph::build_with_rules(tasks, &config.folder_rules);
+    let document = JsonDocument {
+        schema_version: JSON_SCHEMA_VERSION,
+        generator: "autodomd",
+        generated_at: Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        tasks,
+        edges: graph
+            .edges()
+            .into_iter()
+            .map(|(dependency, task)| JsonEdge {
+                task: &task.id,
+                depends_on: &dependency.id,
+            })
+            .collect(),
+    };
+
+    let mut json = serde_json::to_string_pretty(&document)
+        .map_err(|e| TodoError::InvalidFormat(format!("cannot write tasks as JSON: {}", e)))?;
+    json.push('\n');
+    Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use autodomd_library_common::{TaskCategory, TaskStatus};
+
+    #[test]
+    fn test_json_document() {
+        let mut core = Task::from_markdown(
+            "Core".to_string(),
+            TaskCategory::from_str("Backend/Auth"),
+            "todo/core.md".into(),
+        );
+        core.id = "core".to_string();
+        core.metadata.effort = Some("3d".to_string());
+        let mut docs = Task::from_code(
+            "Write docs".to_string(),
+            TaskCategory::General,
+            "src/lib.rs".into(),
+            7,
+        );
+        docs.id = "todo-1234abcd".to_string();
+        docs.status = TaskStatus::InProgress;
+        docs.metadata.dependencies = vec!["core".to_string()];
+
+        let json = generate_json_content(&[core, docs], &GeneratorConfig::default()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["schema_version"], JSON_SCHEMA_VERSION);
+        assert_eq!(value["tasks"][0]["category"], "Backend/Auth");
+        assert_eq!(value["tasks"][0]["source"], "markdown");
+        assert_eq!(value["tasks"][0]["metadata"]["effort"], "3d");
+        assert_eq!(value["tasks"][1]["location"]["file_path"], "src/lib.rs");
+        assert_eq!(value["tasks"][1]["location"]["line_number"], 7);
+        assert_eq!(value["tasks"][1]["priority"], "medium");
+        assert_eq!(value["tasks"][1]["status"], "in-progress");
+        assert_eq!(value["edges"][0]["task"], "todo-1234abcd");
+        assert_eq!(value["edges"][0]["depends_on"], "core");
+
+        let tasks: Vec<Task> = serde_json::from_value(value["tasks"].clone()).unwrap();
+        assert_eq!(tasks[0].category, TaskCategory::from_str("Backend/Auth"));
+    }
+}
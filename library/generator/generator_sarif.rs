@@ -0,0 +1,214 @@
+//! SARIF 2.1.0 output for code-scanning dashboards
+//!
+//! Each code TODO becomes a result whose rule is its tag (`FIXME`) or its
+//! category (`Backend/Auth`), depending on [`RuleKey`]. Priority sets the
+//! level and the task location becomes the physical location, so review tools
+//! can show TODOs inline. Markdown tasks have no place in the code and are left out.
+
+use autodomd_library_common::{Task, TaskPriority, TaskSource, TodoError, TodoResult};
+use serde_json::{json, Value};
+
+use crate::{relative_path, GeneratorConfig, RuleKey};
+
+/// SARIF version written to the log
+const SARIF_VERSION: &str = "2.1.0";
+
+/// Schema of the SARIF version written
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Render the code tasks as a pretty-printed SARIF log
+pub(crate) fn generate_sarif_content(
+    tasks: &[Task],
+    config: &GeneratorConfig,
+) -> TodoResult<String> {
+    let tasks: Vec<&Task> = tasks
+        .iter()
+        .filter(|task| task.source == TaskSource::Code)
+        .collect();
+
+    let mut rule_ids: Vec<String> = Vec::new();
+    let mut results = Vec::new();
+    for task in &tasks {
+        let rule_id = config.rule_key.rule_id(task);
+        let rule_index = match rule_ids.iter().position(|id| *id == rule_id) {
+            Some(index) => index,
+            None => {
+                rule_ids.push(rule_id.clone());
+                rule_ids.len() - 1
+            }
+        };
+        results.push(result(task, rule_id, rule_index, config));
+    }
+
+    let rules: Vec<Value> = rule_ids
+        .iter()
+        .map(|id| {
+            let description = match config.rule_key {
+                RuleKey::Tag => format!("{} comment", id),
+                RuleKey::Category => format!("TODO in category {}", id),
+            };
+            json!({ "id": id, "shortDescription": { "text": description } })
+        })
+        .collect();
+
+    let log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "autodomd",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    });
+
+    let mut sarif = serde_json::to_string_pretty(&log)
+        .map_err(|e| TodoError::InvalidFormat(format!("cannot write tasks as SARIF: {}", e)))?;
+    sarif.push('\n');
+    Ok(sarif)
+}
+
+/// One SARIF result for a code task
+fn result(task: &Task, rule_id: String, rule_index: usize, config: &GeneratorConfig) -> Value {
+    let mut region = serde_json::Map::new();
+    if let Some(line) = task.location.line_number {
+        region.insert("startLine".to_string(), json!(line));
+    }
+    if let Some(column) = task.location.column_number {
+        region.insert("startColumn".to_string(), json!(column));
+    }
+    if let Some(end_line) = task.location.end_line_number {
+        region.insert("endLine".to_string(), json!(end_line));
+    }
+
+    json!({
+        "ruleId": rule_id,
+        "ruleIndex": rule_index,
+        "level": level(task.priority),
+        "message": { "text": task.title },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": { "uri": artifact_uri(task, config), "uriBaseId": "%SRCROOT%" },
+                "region": region,
+            }
+        }],
+        "partialFingerprints": { "autodomdTaskId/v1": task.id },
+    })
+}
+
+/// SARIF level for a task priority
+fn level(priority: TaskPriority) -> &'static str {
+    match priority {
+        TaskPriority::High => "error",
+        TaskPriority::Medium => "warning",
+        TaskPriority::Low => "note",
+    }
+}
+
+/// Percent-encoded URI of the task's file, relative to the scan root
+fn artifact_uri(task: &Task, config: &GeneratorConfig) -> String {
+    let mut uri = String::new();
+    for byte in relative_path(task, &config.root_path).bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use autodomd_library_common::{TaskCategory, TaskLocation};
+
+    fn tasks() -> Vec<Task> {
+        let mut fixme = Task::new(
+            "Crash on empty input".to_string(),
+            TaskCategory::from_str("Backend/Auth"),
+            TaskPriority::High,
+            TaskLocation::from_source_range("./src/my file.rs".into(), 3, 4).with_column(8),
+            TaskSource::Code,
+        );
+        fixme.id = "fixme-1234abcd".to_string();
+        fixme.tag = Some("FIXME".to_string());
+
+        let mut note = Task::from_code(
+            "Document this".to_string(),
+            TaskCategory::General,
+            "src/lib.rs".into(),
+            9,
+        );
+        note.priority = TaskPriority::Low;
+        note.tag = Some("NOTE".to_string());
+
+        let markdown = Task::from_markdown(
+            "Plan".to_string(),
+            TaskCategory::General,
+            "todo/plan.md".into(),
+        );
+        vec![fixme, note, markdown]
+    }
+
+    #[test]
+    fn test_sarif_log() {
+        let sarif = generate_sarif_content(&tasks(), &GeneratorConfig::default()).unwrap();
+        let value: Value = serde_json::from_str(&sarif).unwrap();
+        let run = &value["runs"][0];
+
+        assert_eq!(value["version"], "2.1.0");
+        assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "FIXME");
+        assert_eq!(run["tool"]["driver"]["rules"][1]["id"], "NOTE");
+        assert_eq!(run["results"].as_array().unwrap().len(), 2);
+
+        let result = &run["results"][0];
+        assert_eq!(result["ruleId"], "FIXME");
+        assert_eq!(result["level"], "error");
+        assert_eq!(result["message"]["text"], "Crash on empty input");
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/my%20file.rs");
+        assert_eq!(location["region"]["startLine"], 3);
+        assert_eq!(location["region"]["endLine"], 4);
+        assert_eq!(location["region"]["startColumn"], 8);
+        assert_eq!(run["results"][1]["level"], "note");
+    }
+
+    #[test]
+    fn test_sarif_rules_by_category() {
+        let config = GeneratorConfig {
+            rule_key: RuleKey::Category,
+            ..GeneratorConfig::default()
+        };
+        let sarif = generate_sarif_content(&tasks(), &config).unwrap();
+        let value: Value = serde_json::from_str(&sarif).unwrap();
+
+        assert_eq!(value["runs"][0]["results"][0]["ruleId"], "Backend/Auth");
+        assert_eq!(value["runs"][0]["results"][1]["ruleId"], "General");
+        assert_eq!(value["runs"][0]["results"][1]["ruleIndex"], 1);
+    }
+
+    #[test]
+    fn test_sarif_uris_relative_to_absolute_root() {
+        let task = Task::from_code(
+            "Fix".to_string(),
+            TaskCategory::General,
+            "/work/proj/src/lib.rs".into(),
+            1,
+        );
+        let config = GeneratorConfig {
+            root_path: "/work/proj".into(),
+            ..GeneratorConfig::default()
+        };
+        let sarif = generate_sarif_content(&[task], &config).unwrap();
+        let value: Value = serde_json::from_str(&sarif).unwrap();
+        let location = &value["runs"][0]["results"][0]["locations"][0]["physicalLocation"];
+
+        assert_eq!(location["artifactLocation"]["uri"], "src/lib.rs");
+    }
+}
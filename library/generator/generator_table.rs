@@ -0,0 +1,221 @@
+//! CSV and TSV export, one row per task
+//!
+//! Columns are chosen by name (see [`TABLE_COLUMNS`]); `custom.KEY` adds a
+//! column for the custom frontmatter key `KEY`. List values such as
+//! dependencies are joined with `, ` inside a single cell.
+
+use autodomd_library_common::{Task, TaskSource, TodoError, TodoResult};
+
+use crate::{relative_path, GeneratorConfig};
+
+/// Column names understood by the table exporter
+pub const TABLE_COLUMNS: &[&str] = &[
+    "id",
+    "title",
+    "category",
+    "priority",
+    "source",
+    "file",
+    "line",
+    "end_line",
+    "tag",
+    "owner",
+    "status",
+    "blocked_reason",
+    "effort",
+    "effort_hours",
+    "dependencies",
+    "blocks",
+    "tags",
+    "issues",
+    "due",
+    "subtasks",
+];
+
+/// Columns written when none are configured
+pub const DEFAULT_TABLE_COLUMNS: &[&str] = &[
+    "id",
+    "title",
+    "category",
+    "priority",
+    "source",
+    "file",
+    "line",
+    "status",
+    "effort",
+    "dependencies",
+];
+
+/// Prefix selecting a custom frontmatter key as a column
+const CUSTOM_PREFIX: &str = "custom.";
+
+/// Render the tasks as a table, separated by `delimiter` (`,` for CSV, tab for TSV)
+pub(crate) fn generate_table_content(
+    tasks: &[Task],
+    config: &GeneratorConfig,
+    delimiter: char,
+) -> TodoResult<String> {
+    let columns: Vec<&str> = if config.columns.is_empty() {
+        DEFAULT_TABLE_COLUMNS.to_vec()
+    } else {
+        config.columns.iter().map(|column| column.trim()).collect()
+    };
+
+    if let Some(unknown) = columns
+        .iter()
+        .find(|column| !TABLE_COLUMNS.contains(column) && !column.starts_with(CUSTOM_PREFIX))
+    {
+        return Err(TodoError::InvalidFormat(format!(
+            "unknown column `{}` (expected one of {}, or custom.KEY)",
+            unknown,
+            TABLE_COLUMNS.join(", ")
+        )));
+    }
+
+    let mut table = String::new();
+    push_row(
+        &mut table,
+        columns.iter().map(|column| column.to_string()),
+        delimiter,
+    );
+    for task in tasks {
+        push_row(
+            &mut table,
+            columns.iter().map(|column| cell(task, column, config)),
+            delimiter,
+        );
+    }
+    Ok(table)
+}
+
+/// Value of one column for a task
+fn cell(task: &Task, column: &str, config: &GeneratorConfig) -> String {
+    if let Some(key) = column.strip_prefix(CUSTOM_PREFIX) {
+        return task.metadata.custom.get(key).cloned().unwrap_or_default();
+    }
+
+    let optional = |value: Option<usize>| value.map(|value| value.to_string()).unwrap_or_default();
+    match column {
+        "id" => task.id.clone(),
+        "title" => task.title.clone(),
+        "category" => task.category.display_name(),
+        "priority" => task.priority.to_string(),
+        "source" => match task.source {
+            TaskSource::Markdown => "markdown".to_string(),
+            TaskSource::Code => "code".to_string(),
+        },
+        "file" => relative_path(task, &config.root_path),
+        "line" => optional(task.location.line_number),
+        "end_line" => optional(task.location.end_line_number),
+        "tag" => task.tag.clone().unwrap_or_default(),
+        "owner" => task.assignee.clone().unwrap_or_default(),
+        "status" => task.status.to_string(),
+        "blocked_reason" => task.blocked_reason.clone().unwrap_or_default(),
+        "effort" => task.metadata.effort.clone().unwrap_or_default(),
+        "effort_hours" => config
+            .effort_scale
+            .task_hours(task)
+            .map(|hours| ((hours * 10.0).round() / 10.0).to_string())
+            .unwrap_or_default(),
+        "dependencies" => task.metadata.dependencies.join(", "),
+        "blocks" => task.metadata.blocks.join(", "),
+        "tags" => task.metadata.tags.join(", "),
+        "issues" => task.issues.join(", "),
+        "due" => task
+            .due_date
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .unwrap_or_default(),
+        "subtasks" if task.subtasks.is_empty() => String::new(),
+        "subtasks" => format!("{}/{}", task.completed_subtasks(), task.subtasks.len()),
+        _ => String::new(),
+    }
+}
+
+/// Append one row, quoting (CSV) or flattening (TSV) cells as the delimiter requires
+fn push_row(table: &mut String, cells: impl Iterator<Item = String>, delimiter: char) {
+    let cells: Vec<String> = cells
+        .map(|cell| {
+            if delimiter == '\t' {
+                // TSV has no quoting, so tabs and line breaks become spaces
+                cell.replace(['\t', '\r', '\n'], " ")
+            } else if cell.contains([delimiter, '"', '\r', '\n']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell
+            }
+        })
+        .collect();
+    table.push_str(&cells.join(&delimiter.to_string()));
+    table.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use autodomd_library_common::{TaskCategory, TaskStatus};
+
+    fn tasks() -> Vec<Task> {
+        let mut login = Task::from_markdown(
+            "Login, \"quick\" version".to_string(),
+            TaskCategory::from_str("Backend/Auth"),
+            "./todo/login.md".into(),
+        );
+        login.id = "login".to_string();
+        login.status = TaskStatus::InProgress;
+        login.metadata.effort = Some("m".to_string());
+        login.metadata.dependencies = vec!["core".to_string(), "db".to_string()];
+        login
+            .metadata
+            .custom
+            .insert("quarter".to_string(), "Q3".to_string());
+
+        let mut fixme = Task::from_code(
+            "Crash\ton empty input".to_string(),
+            TaskCategory::General,
+            "src/a.rs".into(),
+            3,
+        );
+        fixme.id = "fixme-1234abcd".to_string();
+        vec![login, fixme]
+    }
+
+    #[test]
+    fn test_csv_export() {
+        let csv = generate_table_content(&tasks(), &GeneratorConfig::default(), ',').unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "id,title,category,priority,source,file,line,status,effort,dependencies"
+        );
+        assert_eq!(
+            lines[1],
+            "login,\"Login, \"\"quick\"\" version\",Backend/Auth,Medium,markdown,todo/login.md,,In Progress,m,\"core, db\""
+        );
+        assert_eq!(
+            lines[2],
+            "fixme-1234abcd,Crash\ton empty input,General,Medium,code,src/a.rs,3,Open,,"
+        );
+    }
+
+    #[test]
+    fn test_tsv_export_with_columns() {
+        let config = GeneratorConfig {
+            columns: vec![
+                "title".to_string(),
+                "effort_hours".to_string(),
+                "custom.quarter".to_string(),
+            ],
+            ..GeneratorConfig::default()
+        };
+        let tsv = generate_table_content(&tasks(), &config, '\t').unwrap();
+
+        assert_eq!(tsv, "title\teffort_hours\tcustom.quarter\nLogin, \"quick\" version\t12\tQ3\nCrash on empty input\t\t\n");
+
+        let config = GeneratorConfig {
+            columns: vec!["colour".to_string()],
+            ..GeneratorConfig::default()
+        };
+        assert!(generate_table_content(&tasks(), &config, ',').is_err());
+    }
+}
@@ -0,0 +1,405 @@
+//! Task dependency graph for autodomd
+//!
+//! This microcrate links tasks by ID into a dependency graph and checks it.
+//! Edges come from three places:
+//!
+//! - a task's `dependencies` (the task depends on each entry)
+//! - a task's `blocks` (each entry depends on the task)
+//! - the folder layout, under configurable [`FolderRules`]
+//!   (`todo/060-git-integration/*.md` depends on `todo/060-git-integration.md`)
+//!
+//! A task's level is the length of the longest dependency chain below it, so
+//! level 0 tasks depend on nothing and every task sits above all of its
+//! dependencies. Tasks in a cycle share a level.
+//!
+//! The graph can be exported as a Mermaid flowchart or a Graphviz DOT document
+//! ([`TaskGraph::to_mermaid`], [`TaskGraph::to_dot`]), and [`TaskGraph::plan`]
+//! finds the critical path, the tasks ready to start and what each task unblocks.
+
+mod graph_cycles;
+mod graph_export;
+mod graph_folders;
+mod graph_plan;
+
+use std::collections::HashMap;
+use std::fmt;
+
+use autodomd_library_common::Task;
+
+use graph_cycles::{cycle_path, strongly_connected_components};
+use graph_folders::folder_parents;
+
+pub use graph_folders::FolderRules;
+pub use graph_plan::{Plan, Unblock};
+
+/// A problem found while building the graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphProblem {
+    /// Tasks that depend on each other in a loop, as IDs with the first one repeated at the end
+    Cycle(Vec<String>),
+    /// A `dependencies` or `blocks` entry that names no known task
+    DanglingReference {
+        task: String,
+        key: &'static str,
+        reference: String,
+    },
+    /// `task` lists `other` under `key`, but `other` does not list `task` under the inverse key
+    OneSided {
+        task: String,
+        key: &'static str,
+        other: String,
+    },
+}
+
+impl fmt::Display for GraphProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphProblem::Cycle(path) => write!(f, "dependency cycle: {}", path.join(" -> ")),
+            GraphProblem::DanglingReference {
+                task,
+                key,
+                reference,
+            } => {
+                write!(
+                    f,
+                    "`{}` lists `{}` in {}, but no task has that ID",
+                    task, reference, key
+                )
+            }
+            GraphProblem::OneSided { task, key, other } => write!(
+                f,
+                "`{}` lists `{}` in {}, but `{}` does not list `{}` in {}",
+                task,
+                other,
+                key,
+                other,
+                task,
+                inverse_key(key)
+            ),
+        }
+    }
+}
+
+/// Dependency graph over a set of tasks
+#[derive(Debug)]
+pub struct TaskGraph<'a> {
+    tasks: &'a [Task],
+    index: HashMap<&'a str, usize>,
+    /// Tasks each task depends on, by index
+    dependencies: Vec<Vec<usize>>,
+    /// Tasks depending on each task, by index
+    dependents: Vec<Vec<usize>>,
+    /// Longest dependency chain below each task
+    levels: Vec<usize>,
+    problems: Vec<GraphProblem>,
+}
+
+impl<'a> TaskGraph<'a> {
+    /// Build the graph with the default folder rules, resolving references by task ID
+    pub fn build(tasks: &'a [Task]) -> Self {
+        Self::build_with_rules(tasks, &FolderRules::default())
+    }
+
+    /// Build the graph with the given folder rules
+    pub fn build_with_rules(tasks: &'a [Task], rules: &FolderRules) -> Self {
+        let mut index = HashMap::new();
+        for (i, task) in tasks.iter().enumerate() {
+            index.entry(task.id.as_str()).or_insert(i);
+        }
+
+        let mut graph = Self {
+            tasks,
+            index,
+            dependencies: vec![Vec::new(); tasks.len()],
+            dependents: vec![Vec::new(); tasks.len()],
+            levels: vec![0; tasks.len()],
+            problems: Vec::new(),
+        };
+        graph.add_edges(rules);
+        graph.compute_levels();
+        graph
+    }
+
+    /// All tasks in the graph
+    pub fn tasks(&self) -> &'a [Task] {
+        self.tasks
+    }
+
+    /// Find a task by ID
+    pub fn task(&self, id: &str) -> Option<&'a Task> {
+        self.index.get(id).map(|&i| &self.tasks[i])
+    }
+
+    /// Tasks that must be done before the given task
+    pub fn dependencies(&self, id: &str) -> Vec<&'a Task> {
+        self.neighbours(id, &self.dependencies)
+    }
+
+    /// Tasks waiting on the given task
+    pub fn dependents(&self, id: &str) -> Vec<&'a Task> {
+        self.neighbours(id, &self.dependents)
+    }
+
+    /// Length of the longest dependency chain below a task (0 for unknown IDs)
+    pub fn level(&self, id: &str) -> usize {
+        self.index.get(id).map(|&i| self.levels[i]).unwrap_or(0)
+    }
+
+    /// Tasks ordered so each comes after its dependencies (cycles aside),
+    /// keeping the input order within a level
+    pub fn topological_order(&self) -> Vec<&'a Task> {
+        let mut order: Vec<usize> = (0..self.tasks.len()).collect();
+        order.sort_by_key(|&i| self.levels[i]);
+        order.into_iter().map(|i| &self.tasks[i]).collect()
+    }
+
+    /// Every edge as `(dependency, dependent)`, in task order
+    pub fn edges(&self) -> Vec<(&'a Task, &'a Task)> {
+        self.edge_indexes()
+            .into_iter()
+            .map(|(dependency, task)| (&self.tasks[dependency], &self.tasks[task]))
+            .collect()
+    }
+
+    /// Every `(dependency, dependent)` pair, by task index
+    fn edge_indexes(&self) -> Vec<(usize, usize)> {
+        self.dependencies
+            .iter()
+            .enumerate()
+            .flat_map(|(task, dependencies)| {
+                dependencies
+                    .iter()
+                    .map(move |&dependency| (dependency, task))
+            })
+            .collect()
+    }
+
+    /// Cycles, dangling references and one-sided relationships
+    pub fn problems(&self) -> &[GraphProblem] {
+        &self.problems
+    }
+
+    fn neighbours(&self, id: &str, edges: &[Vec<usize>]) -> Vec<&'a Task> {
+        self.index
+            .get(id)
+            .map(|&i| edges[i].iter().map(|&j| &self.tasks[j]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Add edges from metadata and folder layout, recording bad references
+    fn add_edges(&mut self, rules: &FolderRules) {
+        for (i, parent) in folder_parents(self.tasks, rules).into_iter().enumerate() {
+            if let Some(parent) = parent.filter(|&parent| parent != i) {
+                self.add_edge(i, parent);
+            }
+        }
+
+        for (i, task) in self.tasks.iter().enumerate() {
+            for reference in &task.metadata.dependencies {
+                if let Some(dependency) = self.resolve(task, "dependencies", reference) {
+                    self.add_edge(i, dependency);
+                    self.check_inverse(task, "dependencies", dependency);
+                }
+            }
+
+            for reference in &task.metadata.blocks {
+                if let Some(dependent) = self.resolve(task, "blocks", reference) {
+                    self.add_edge(dependent, i);
+                    self.check_inverse(task, "blocks", dependent);
+                }
+            }
+        }
+    }
+
+    fn resolve(&mut self, task: &Task, key: &'static str, reference: &str) -> Option<usize> {
+        let reference = reference.trim();
+        let resolved = self.index.get(reference).copied();
+        if resolved.is_none() {
+            self.problems.push(GraphProblem::DanglingReference {
+                task: task.id.clone(),
+                key,
+                reference: reference.to_string(),
+            });
+        }
+        resolved
+    }
+
+    /// Record a relationship the other task does not declare in return
+    fn check_inverse(&mut self, task: &Task, key: &'static str, other: usize) {
+        let other = &self.tasks[other];
+        let inverse = match key {
+            "dependencies" => &other.metadata.blocks,
+            _ => &other.metadata.dependencies,
+        };
+
+        if !inverse.iter().any(|reference| reference.trim() == task.id) {
+            self.problems.push(GraphProblem::OneSided {
+                task: task.id.clone(),
+                key,
+                other: other.id.clone(),
+            });
+        }
+    }
+
+    fn add_edge(&mut self, task: usize, dependency: usize) {
+        if !self.dependencies[task].contains(&dependency) {
+            self.dependencies[task].push(dependency);
+            self.dependents[dependency].push(task);
+        }
+    }
+
+    /// Assign levels over the graph's strongly connected components, reporting cycles
+    fn compute_levels(&mut self) {
+        let components = strongly_connected_components(&self.dependencies);
+
+        let mut component_of = vec![0; self.tasks.len()];
+        for (c, component) in components.iter().enumerate() {
+            for &task in component {
+                component_of[task] = c;
+            }
+        }
+
+        // Components come out dependencies-first, so each level is final when reached
+        for (c, component) in components.iter().enumerate() {
+            let level = component
+                .iter()
+                .flat_map(|&task| &self.dependencies[task])
+                .filter(|&&dependency| component_of[dependency] != c)
+                .map(|&dependency| self.levels[dependency] + 1)
+                .max()
+                .unwrap_or(0);
+            for &task in component {
+                self.levels[task] = level;
+            }
+
+            if let Some(path) = cycle_path(component, &self.dependencies) {
+                let ids = path
+                    .into_iter()
+                    .map(|task| self.tasks[task].id.clone())
+                    .collect();
+                self.problems.push(GraphProblem::Cycle(ids));
+            }
+        }
+    }
+}
+
+fn inverse_key(key: &str) -> &'static str {
+    match key {
+        "dependencies" => "blocks",
+        _ => "dependencies",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use autodomd_library_common::TaskCategory;
+
+    fn task(id: &str, path: &str, dependencies: &[&str], blocks: &[&str]) -> Task {
+        let mut task = Task::from_markdown(id.to_string(), TaskCategory::General, path.into());
+        task.id = id.to_string();
+        task.metadata.dependencies = dependencies.iter().map(|d| d.to_string()).collect();
+        task.metadata.blocks = blocks.iter().map(|b| b.to_string()).collect();
+        task
+    }
+
+    fn ids(tasks: Vec<&Task>) -> Vec<&str> {
+        tasks.into_iter().map(|task| task.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_levels_follow_longest_chain() {
+        // d depends on a directly and on a through b and c
+        let tasks = vec![
+            task("d", "todo/d.md", &["a", "c"], &[]),
+            task("c", "todo/c.md", &["b"], &["d"]),
+            task("b", "todo/b.md", &["a"], &["c"]),
+            task("a", "todo/a.md", &[], &["b", "d"]),
+            task("e", "todo/e.md", &[], &[]),
+        ];
+        let graph = TaskGraph::build(&tasks);
+
+        assert_eq!(graph.level("a"), 0);
+        assert_eq!(graph.level("e"), 0);
+        assert_eq!(graph.level("b"), 1);
+        assert_eq!(graph.level("c"), 2);
+        assert_eq!(graph.level("d"), 3);
+        assert_eq!(
+            ids(graph.topological_order()),
+            vec!["a", "e", "b", "c", "d"]
+        );
+        assert_eq!(ids(graph.dependents("a")), vec!["d", "b"]);
+        assert!(graph.problems().is_empty());
+    }
+
+    #[test]
+    fn test_cycles_are_reported_with_their_path() {
+        let tasks = vec![
+            task("a", "todo/a.md", &["c"], &["b"]),
+            task("b", "todo/b.md", &["a"], &["c"]),
+            task("c", "todo/c.md", &["b"], &["a"]),
+            task("d", "todo/d.md", &["a"], &[]),
+            task("self", "todo/self.md", &["self"], &["self"]),
+        ];
+        let graph = TaskGraph::build(&tasks);
+
+        let cycles: Vec<String> = graph
+            .problems()
+            .iter()
+            .filter(|problem| matches!(problem, GraphProblem::Cycle(_)))
+            .map(|problem| problem.to_string())
+            .collect();
+        assert_eq!(
+            cycles,
+            vec![
+                "dependency cycle: a -> c -> b -> a",
+                "dependency cycle: self -> self"
+            ]
+        );
+        assert_eq!(graph.level("a"), graph.level("c"));
+        assert_eq!(graph.level("d"), graph.level("a") + 1);
+    }
+
+    #[test]
+    fn test_dangling_and_one_sided_references() {
+        let tasks = vec![
+            task("core", "todo/010-core.md", &[], &["docs", "release"]),
+            task("docs", "todo/020-docs.md", &["core", "missing"], &[]),
+            task("release", "todo/030-release.md", &[], &["ghost"]),
+        ];
+        let graph = TaskGraph::build(&tasks);
+
+        let problems: Vec<String> = graph
+            .problems()
+            .iter()
+            .map(|problem| problem.to_string())
+            .collect();
+        assert_eq!(
+            problems,
+            vec![
+                "`core` lists `release` in blocks, but `release` does not list `core` in dependencies",
+                "`docs` lists `missing` in dependencies, but no task has that ID",
+                "`release` lists `ghost` in blocks, but no task has that ID",
+            ]
+        );
+        assert_eq!(graph.level("release"), 1);
+    }
+
+    #[test]
+    fn test_folder_parent() {
+        let tasks = vec![
+            task("ci", "todo/060-git-integration/010-ci.md", &[], &[]),
+            task("git", "todo/060-git-integration.md", &[], &[]),
+            task("config", "todo/070-advanced/010-config.md", &[], &[]),
+        ];
+        let graph = TaskGraph::build(&tasks);
+
+        assert_eq!(ids(graph.dependencies("ci")), vec!["git"]);
+        assert_eq!(graph.level("ci"), 1);
+        assert!(graph.dependencies("config").is_empty());
+        assert!(graph.problems().is_empty());
+
+        let graph = TaskGraph::build_with_rules(&tasks, &FolderRules::disabled());
+        assert!(graph.dependencies("ci").is_empty());
+    }
+}
@@ -0,0 +1,151 @@
+//! Strongly connected components and cycle paths
+//!
+//! Graphs are adjacency lists indexed by task, where `edges[task]` holds the
+//! tasks it depends on.
+
+use std::collections::VecDeque;
+
+/// Tarjan's algorithm, returning components dependencies-first
+pub(crate) fn strongly_connected_components(edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut tarjan = Tarjan {
+        edges,
+        next_index: 0,
+        index: vec![None; edges.len()],
+        low_link: vec![0; edges.len()],
+        on_stack: vec![false; edges.len()],
+        stack: Vec::new(),
+        components: Vec::new(),
+    };
+
+    for node in 0..edges.len() {
+        if tarjan.index[node].is_none() {
+            tarjan.visit(node);
+        }
+    }
+    tarjan.components
+}
+
+struct Tarjan<'e> {
+    edges: &'e [Vec<usize>],
+    next_index: usize,
+    index: Vec<Option<usize>>,
+    low_link: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    components: Vec<Vec<usize>>,
+}
+
+impl Tarjan<'_> {
+    /// Visit every node reachable from `root`
+    ///
+    /// Uses a work stack of `(node, next edge)` pairs instead of recursion, so a
+    /// long dependency chain cannot overflow the call stack.
+    fn visit(&mut self, root: usize) {
+        self.discover(root);
+        let mut work = vec![(root, 0)];
+
+        while let Some((node, edge)) = work.last_mut() {
+            let node = *node;
+            if let Some(&next) = self.edges[node].get(*edge) {
+                *edge += 1;
+                match self.index[next] {
+                    None => {
+                        self.discover(next);
+                        work.push((next, 0));
+                    }
+                    Some(next_index) if self.on_stack[next] => {
+                        self.low_link[node] = self.low_link[node].min(next_index);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            work.pop();
+            if let Some(&(parent, _)) = work.last() {
+                self.low_link[parent] = self.low_link[parent].min(self.low_link[node]);
+            }
+            if Some(self.low_link[node]) == self.index[node] {
+                self.pop_component(node);
+            }
+        }
+    }
+
+    fn discover(&mut self, node: usize) {
+        self.index[node] = Some(self.next_index);
+        self.low_link[node] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+    }
+
+    /// Pop the component whose first visited node is `root`
+    fn pop_component(&mut self, root: usize) {
+        let mut component = Vec::new();
+        while let Some(member) = self.stack.pop() {
+            self.on_stack[member] = false;
+            component.push(member);
+            if member == root {
+                break;
+            }
+        }
+        component.sort_unstable();
+        self.components.push(component);
+    }
+}
+
+/// Shortest cycle through the component's first node, with that node repeated
+/// at the end, or `None` if the component is not cyclic
+pub(crate) fn cycle_path(component: &[usize], edges: &[Vec<usize>]) -> Option<Vec<usize>> {
+    let &start = component.first()?;
+    if component.len() == 1 && !edges[start].contains(&start) {
+        return None;
+    }
+
+    // Breadth-first search inside the component for the way back to `start`
+    let mut previous: Vec<Option<usize>> = vec![None; edges.len()];
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        for &next in &edges[node] {
+            if next == start {
+                let mut path = vec![start];
+                let mut current = node;
+                while current != start {
+                    path.push(current);
+                    current = previous[current]?;
+                }
+                path[1..].reverse();
+                path.push(start);
+                return Some(path);
+            }
+            if component.contains(&next) && previous[next].is_none() {
+                previous[next] = Some(node);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_long_chains_do_not_overflow() {
+        // Each task depends on the next, closing into one long cycle
+        let length = 200_000;
+        let mut edges: Vec<Vec<usize>> = (1..=length).map(|next| vec![next]).collect();
+        edges[length - 1] = vec![0];
+
+        let components = strongly_connected_components(&edges);
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].len(), length);
+
+        edges[length - 1].clear();
+        let components = strongly_connected_components(&edges);
+        assert_eq!(components.len(), length);
+        assert_eq!(components[0], vec![length - 1]);
+    }
+}
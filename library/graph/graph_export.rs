@@ -0,0 +1,216 @@
+//! Export of the dependency graph as Mermaid or Graphviz DOT
+//!
+//! Both formats draw an edge from each dependency to the task waiting on it,
+//! group tasks into one box per category, fill nodes by priority and outline
+//! them by status (thick red for blocked, dashed grey for done).
+
+use std::collections::BTreeMap;
+
+use autodomd_library_common::{Task, TaskPriority, TaskStatus};
+
+use crate::TaskGraph;
+
+/// Longest node label before it is shortened
+const MAX_LABEL_CHARS: usize = 60;
+
+impl TaskGraph<'_> {
+    /// Render the graph as a Mermaid `flowchart` (without the code fence)
+    pub fn to_mermaid(&self) -> String {
+        let mut out = String::from("flowchart TD\n");
+
+        for (c, (category, tasks)) in self.tasks_by_category().into_iter().enumerate() {
+            out.push_str(&format!(
+                "    subgraph c{}[\"{}\"]\n",
+                c,
+                mermaid_escape(&category)
+            ));
+            for (i, task) in tasks {
+                out.push_str(&format!(
+                    "        t{}[\"{}\"]\n",
+                    i,
+                    mermaid_escape(&label(task))
+                ));
+            }
+            out.push_str("    end\n");
+        }
+
+        for (dependency, task) in self.edge_indexes() {
+            out.push_str(&format!("    t{} --> t{}\n", dependency, task));
+        }
+
+        out.push_str("    classDef high fill:#f8d7da,stroke:#333\n");
+        out.push_str("    classDef medium fill:#fff3cd,stroke:#333\n");
+        out.push_str("    classDef low fill:#d1e7dd,stroke:#333\n");
+        out.push_str("    classDef inprogress stroke:#0d6efd,stroke-width:3px\n");
+        out.push_str("    classDef blocked stroke:#dc3545,stroke-width:3px\n");
+        out.push_str("    classDef done stroke:#999,stroke-dasharray:5 5,color:#777\n");
+        for (i, task) in self.tasks().iter().enumerate() {
+            out.push_str(&format!(
+                "    class t{} {}\n",
+                i,
+                priority_class(task.priority)
+            ));
+            if let Some(class) = status_class(task.status) {
+                out.push_str(&format!("    class t{} {}\n", i, class));
+            }
+        }
+
+        out
+    }
+
+    /// Render the graph as a standalone Graphviz DOT document
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph tasks {\n");
+        out.push_str("    rankdir=LR;\n");
+        out.push_str("    node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];\n");
+
+        for (c, (category, tasks)) in self.tasks_by_category().into_iter().enumerate() {
+            out.push_str(&format!("    subgraph cluster_{} {{\n", c));
+            out.push_str(&format!("        label=\"{}\";\n", dot_escape(&category)));
+            for (_, task) in tasks {
+                let (color, penwidth, style) = match task.status {
+                    TaskStatus::InProgress => ("#0d6efd", 3, "rounded,filled"),
+                    TaskStatus::Blocked => ("#dc3545", 3, "rounded,filled"),
+                    TaskStatus::Done => ("#999999", 1, "rounded,filled,dashed"),
+                    TaskStatus::Open => ("#333333", 1, "rounded,filled"),
+                };
+                out.push_str(&format!(
+                    "        \"{}\" [label=\"{}\", fillcolor=\"{}\", color=\"{}\", penwidth={}, style=\"{}\"];\n",
+                    dot_escape(&task.id),
+                    dot_escape(&label(task)),
+                    priority_fill(task.priority),
+                    color,
+                    penwidth,
+                    style
+                ));
+            }
+            out.push_str("    }\n");
+        }
+
+        for (dependency, task) in self.edge_indexes() {
+            out.push_str(&format!(
+                "    \"{}\" -> \"{}\";\n",
+                dot_escape(&self.tasks()[dependency].id),
+                dot_escape(&self.tasks()[task].id)
+            ));
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Tasks with their indexes, grouped by category name
+    fn tasks_by_category(&self) -> BTreeMap<String, Vec<(usize, &Task)>> {
+        let mut groups: BTreeMap<String, Vec<(usize, &Task)>> = BTreeMap::new();
+        for (i, task) in self.tasks().iter().enumerate() {
+            groups
+                .entry(task.category.display_name())
+                .or_default()
+                .push((i, task));
+        }
+        groups
+    }
+}
+
+/// Node label: the task title, shortened when long
+fn label(task: &Task) -> String {
+    if task.title.chars().count() <= MAX_LABEL_CHARS {
+        return task.title.clone();
+    }
+    let mut shortened: String = task.title.chars().take(MAX_LABEL_CHARS - 1).collect();
+    shortened.push('…');
+    shortened
+}
+
+fn priority_class(priority: TaskPriority) -> &'static str {
+    match priority {
+        TaskPriority::High => "high",
+        TaskPriority::Medium => "medium",
+        TaskPriority::Low => "low",
+    }
+}
+
+fn priority_fill(priority: TaskPriority) -> &'static str {
+    match priority {
+        TaskPriority::High => "#f8d7da",
+        TaskPriority::Medium => "#fff3cd",
+        TaskPriority::Low => "#d1e7dd",
+    }
+}
+
+fn status_class(status: TaskStatus) -> Option<&'static str> {
+    match status {
+        TaskStatus::Open => None,
+        TaskStatus::InProgress => Some("inprogress"),
+        TaskStatus::Blocked => Some("blocked"),
+        TaskStatus::Done => Some("done"),
+    }
+}
+
+/// Escape text for a quoted Mermaid label
+fn mermaid_escape(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
+
+/// Escape text for a quoted DOT string
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use autodomd_library_common::TaskCategory;
+
+    fn tasks() -> Vec<Task> {
+        let mut core = Task::from_markdown(
+            "Core \"engine\"".to_string(),
+            TaskCategory::from_str("Backend"),
+            "todo/core.md".into(),
+        );
+        core.id = "core".to_string();
+        core.priority = TaskPriority::High;
+        core.metadata.blocks = vec!["docs".to_string()];
+
+        let mut docs = Task::from_markdown(
+            "Docs".to_string(),
+            TaskCategory::General,
+            "todo/docs.md".into(),
+        );
+        docs.id = "docs".to_string();
+        docs.status = TaskStatus::Blocked;
+        docs.metadata.dependencies = vec!["core".to_string()];
+
+        vec![core, docs]
+    }
+
+    #[test]
+    fn test_mermaid_export() {
+        let tasks = tasks();
+        let mermaid = TaskGraph::build(&tasks).to_mermaid();
+
+        assert!(mermaid.starts_with("flowchart TD\n"));
+        assert!(mermaid.contains(
+            "    subgraph c0[\"Backend\"]\n        t0[\"Core #quot;engine#quot;\"]\n    end\n"
+        ));
+        assert!(mermaid.contains("    subgraph c1[\"General\"]\n        t1[\"Docs\"]\n    end\n"));
+        assert!(mermaid.contains("    t0 --> t1\n"));
+        assert!(mermaid.contains("    class t0 high\n"));
+        assert!(mermaid.contains("    class t1 medium\n    class t1 blocked\n"));
+    }
+
+    #[test]
+    fn test_dot_export() {
+        let tasks = tasks();
+        let dot = TaskGraph::build(&tasks).to_dot();
+
+        assert!(dot.starts_with("digraph tasks {\n"));
+        assert!(dot.contains("        label=\"Backend\";\n"));
+        assert!(dot.contains("\"core\" [label=\"Core \\\"engine\\\"\", fillcolor=\"#f8d7da\""));
+        assert!(dot.contains(
+            "\"docs\" [label=\"Docs\", fillcolor=\"#fff3cd\", color=\"#dc3545\", penwidth=3"
+        ));
+        assert!(dot.contains("    \"core\" -> \"docs\";\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+}
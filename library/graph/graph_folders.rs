@@ -0,0 +1,222 @@
+//! Folder conventions for task files
+//!
+//! The layout of `todo/` carries structure without any metadata:
+//!
+//! - a numeric `NNN-` prefix orders files and folders (`9-x` before `10-y`)
+//! - a folder's index file is the folder's own task: either a file next to the
+//!   folder with the same name (`060-git-integration.md`) or a file inside it
+//!   with one of the index names (`060-git-integration/index.md`)
+//! - every other file in the folder depends on that index task, and an index
+//!   depends on the index of the folder above it
+//!
+//! Folders without an index fall back to the nearest ancestor that has one.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use autodomd_library_common::{Task, TaskSource};
+
+/// Name of the directory holding markdown task files
+const TODO_DIR: &str = "todo";
+
+/// Which folder conventions apply to a project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FolderRules {
+    /// Whether files depend on their folder's index task
+    pub index_parents: bool,
+    /// File names (without `.md`) that make a file inside a folder its index
+    pub index_names: Vec<String>,
+    /// Whether numeric `NNN-` prefixes order files and folders
+    pub numeric_order: bool,
+}
+
+impl Default for FolderRules {
+    fn default() -> Self {
+        Self {
+            index_parents: true,
+            index_names: vec!["index".to_string(), "README".to_string()],
+            numeric_order: true,
+        }
+    }
+}
+
+impl FolderRules {
+    /// Rules that add no folder dependencies and keep plain path order
+    pub fn disabled() -> Self {
+        Self {
+            index_parents: false,
+            index_names: Vec::new(),
+            numeric_order: false,
+        }
+    }
+
+    /// Compare two task file paths, honouring numeric prefixes when enabled
+    pub fn compare_paths(&self, a: &Path, b: &Path) -> Ordering {
+        if !self.numeric_order {
+            return a.cmp(b);
+        }
+        order_key(a).cmp(&order_key(b)).then_with(|| a.cmp(b))
+    }
+
+    /// Whether a task file is the index of the folder it sits in
+    fn is_inner_index(&self, path: &Path) -> bool {
+        path.file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| {
+                self.index_names
+                    .iter()
+                    .any(|name| name.eq_ignore_ascii_case(stem))
+            })
+    }
+
+    /// Index files that could stand for a folder
+    fn index_candidates(&self, folder: &Path) -> Vec<PathBuf> {
+        let mut sibling = folder.as_os_str().to_owned();
+        sibling.push(".md");
+
+        let mut candidates = vec![PathBuf::from(sibling)];
+        candidates.extend(
+            self.index_names
+                .iter()
+                .map(|name| folder.join(format!("{}.md", name))),
+        );
+        candidates
+    }
+}
+
+/// Parent task of each task under the folder rules, by index
+pub(crate) fn folder_parents(tasks: &[Task], rules: &FolderRules) -> Vec<Option<usize>> {
+    if !rules.index_parents {
+        return vec![None; tasks.len()];
+    }
+
+    let by_path: HashMap<&Path, usize> = tasks
+        .iter()
+        .enumerate()
+        .filter(|(_, task)| task.source == TaskSource::Markdown)
+        .map(|(i, task)| (task.location.file_path.as_path(), i))
+        .collect();
+
+    tasks
+        .iter()
+        .map(|task| {
+            if task.source != TaskSource::Markdown {
+                return None;
+            }
+            let path = task.location.file_path.as_path();
+
+            // An inner index belongs to its folder, so look from the folder above
+            let mut folder = path.parent()?;
+            if rules.is_inner_index(path) {
+                folder = folder.parent()?;
+            }
+
+            while is_below_todo_dir(folder) {
+                let parent = rules
+                    .index_candidates(folder)
+                    .iter()
+                    .find_map(|candidate| by_path.get(candidate.as_path()).copied());
+                if parent.is_some() {
+                    return parent;
+                }
+                folder = folder.parent()?;
+            }
+            None
+        })
+        .collect()
+}
+
+/// Whether a folder sits inside a `todo` directory (rather than being it)
+fn is_below_todo_dir(folder: &Path) -> bool {
+    folder.parent().is_some_and(|parent| {
+        parent
+            .components()
+            .any(|component| component.as_os_str() == TODO_DIR)
+    })
+}
+
+/// Sort key for a path: each component by numeric prefix, then name without `.md`
+///
+/// Numbered entries come before unnumbered ones, and a folder's sibling index
+/// file (`060-x.md`) sorts directly before the folder's contents (`060-x/...`).
+fn order_key(path: &Path) -> Vec<(bool, u64, String)> {
+    path.components()
+        .map(|component| {
+            let name = component.as_os_str().to_string_lossy();
+            let name = name.strip_suffix(".md").unwrap_or(&name);
+            match name.split_once('-') {
+                Some((number, rest))
+                    if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) =>
+                {
+                    (false, number.parse().unwrap_or(u64::MAX), rest.to_string())
+                }
+                _ => (true, 0, name.to_string()),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use autodomd_library_common::TaskCategory;
+
+    fn markdown(path: &str) -> Task {
+        Task::from_markdown(path.to_string(), TaskCategory::General, path.into())
+    }
+
+    #[test]
+    fn test_folder_parents() {
+        let tasks = vec![
+            markdown("./todo/060-git.md"),
+            markdown("./todo/060-git/010-ci.md"),
+            markdown("./todo/060-git/deep/020-release.md"),
+            markdown("./todo/070-docs/index.md"),
+            markdown("./todo/070-docs/010-readme.md"),
+            markdown("./todo/080-misc/010-loose.md"),
+            markdown("./todo/010-core.md"),
+        ];
+
+        let parents = folder_parents(&tasks, &FolderRules::default());
+        assert_eq!(
+            parents,
+            vec![None, Some(0), Some(0), None, Some(3), None, None]
+        );
+
+        let custom = FolderRules {
+            index_names: vec!["_index".to_string()],
+            ..FolderRules::default()
+        };
+        assert_eq!(folder_parents(&tasks, &custom)[4], None);
+        assert!(folder_parents(&tasks, &FolderRules::disabled())
+            .iter()
+            .all(Option::is_none));
+    }
+
+    #[test]
+    fn test_numeric_order() {
+        let mut paths = vec![
+            "todo/notes.md",
+            "todo/10-later.md",
+            "todo/9-sooner/010-child.md",
+            "todo/9-sooner.md",
+            "todo/020-b.md",
+        ];
+
+        paths.sort_by(|a, b| FolderRules::default().compare_paths(Path::new(a), Path::new(b)));
+        assert_eq!(
+            paths,
+            vec![
+                "todo/9-sooner.md",
+                "todo/9-sooner/010-child.md",
+                "todo/10-later.md",
+                "todo/020-b.md",
+                "todo/notes.md"
+            ]
+        );
+
+        paths.sort_by(|a, b| FolderRules::disabled().compare_paths(Path::new(a), Path::new(b)));
+        assert_eq!(paths[0], "todo/020-b.md");
+    }
+}
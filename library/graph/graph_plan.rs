@@ -0,0 +1,214 @@
+//! Planning queries over the dependency graph
+//!
+//! Only remaining work counts: tasks with status `done` are treated as
+//! finished and never appear in a plan. Edges inside a dependency cycle are
+//! ignored, so a cycle cannot make the critical path infinite.
+
+use std::collections::HashSet;
+
+use autodomd_library_common::{Task, TaskStatus};
+
+use crate::TaskGraph;
+
+/// Answers to "what is the longest chain, what can start now, what matters most"
+#[derive(Debug)]
+pub struct Plan<'a> {
+    /// Longest chain of remaining work by effort, first task first
+    pub critical_path: Vec<&'a Task>,
+    /// Total effort along the critical path
+    pub critical_path_effort: f64,
+    /// Open tasks whose dependencies are all done
+    pub ready: Vec<&'a Task>,
+    /// Remaining tasks that other remaining work waits on, most downstream work first
+    pub unblocks: Vec<Unblock<'a>>,
+}
+
+/// Downstream work waiting on one task
+#[derive(Debug)]
+pub struct Unblock<'a> {
+    pub task: &'a Task,
+    /// Remaining tasks that depend on this one, directly or transitively
+    pub downstream_tasks: usize,
+    /// Total effort of those tasks
+    pub downstream_effort: f64,
+}
+
+impl<'a> TaskGraph<'a> {
+    /// Plan the remaining work, weighing each task by `effort`
+    pub fn plan(&self, effort: impl Fn(&Task) -> f64) -> Plan<'a> {
+        let (critical_path, critical_path_effort) = self.critical_path(&effort);
+
+        Plan {
+            critical_path,
+            critical_path_effort,
+            ready: (0..self.tasks.len())
+                .filter(|&i| self.tasks[i].status == TaskStatus::Open)
+                .filter(|&i| {
+                    self.dependencies[i]
+                        .iter()
+                        .all(|&dependency| self.tasks[dependency].status == TaskStatus::Done)
+                })
+                .map(|i| &self.tasks[i])
+                .collect(),
+            unblocks: self.unblocks(&effort),
+        }
+    }
+
+    /// Longest chain of remaining work by summed effort
+    fn critical_path(&self, effort: &impl Fn(&Task) -> f64) -> (Vec<&'a Task>, f64) {
+        let tasks = self.tasks;
+        let mut finish = vec![0.0_f64; tasks.len()];
+        let mut previous: Vec<Option<usize>> = vec![None; tasks.len()];
+
+        // Levels order dependencies first; same-level edges only occur inside cycles
+        let mut order: Vec<usize> = (0..tasks.len()).collect();
+        order.sort_by_key(|&i| self.levels[i]);
+
+        for &i in &order {
+            if tasks[i].status == TaskStatus::Done {
+                continue;
+            }
+            let longest = self.dependencies[i]
+                .iter()
+                .copied()
+                .filter(|&dependency| self.levels[dependency] < self.levels[i])
+                .filter(|&dependency| tasks[dependency].status != TaskStatus::Done)
+                .max_by(|&a, &b| finish[a].total_cmp(&finish[b]));
+
+            previous[i] = longest;
+            finish[i] =
+                effort(&tasks[i]) + longest.map(|dependency| finish[dependency]).unwrap_or(0.0);
+        }
+
+        let end = (0..tasks.len())
+            .filter(|&i| tasks[i].status != TaskStatus::Done)
+            .max_by(|&a, &b| finish[a].total_cmp(&finish[b]).then_with(|| b.cmp(&a)));
+        let Some(end) = end else {
+            return (Vec::new(), 0.0);
+        };
+
+        let mut path = vec![&tasks[end]];
+        let mut current = end;
+        while let Some(before) = previous[current] {
+            path.push(&tasks[before]);
+            current = before;
+        }
+        path.reverse();
+        (path, finish[end])
+    }
+
+    /// Downstream work for every remaining task that has any
+    fn unblocks(&self, effort: &impl Fn(&Task) -> f64) -> Vec<Unblock<'a>> {
+        let mut unblocks: Vec<Unblock<'a>> = (0..self.tasks.len())
+            .filter(|&i| self.tasks[i].status != TaskStatus::Done)
+            .filter_map(|i| {
+                let downstream: Vec<usize> = self
+                    .reachable(i, &self.dependents)
+                    .into_iter()
+                    .filter(|&j| self.tasks[j].status != TaskStatus::Done)
+                    .collect();
+                (!downstream.is_empty()).then(|| Unblock {
+                    task: &self.tasks[i],
+                    downstream_tasks: downstream.len(),
+                    downstream_effort: downstream.iter().map(|&j| effort(&self.tasks[j])).sum(),
+                })
+            })
+            .collect();
+
+        unblocks.sort_by(|a, b| {
+            b.downstream_effort
+                .total_cmp(&a.downstream_effort)
+                .then_with(|| b.downstream_tasks.cmp(&a.downstream_tasks))
+        });
+        unblocks
+    }
+
+    /// Every task reachable from `start` along `edges`, excluding `start`
+    fn reachable(&self, start: usize, edges: &[Vec<usize>]) -> Vec<usize> {
+        let mut seen = HashSet::from([start]);
+        let mut stack = vec![start];
+        let mut found = Vec::new();
+
+        while let Some(node) = stack.pop() {
+            for &next in &edges[node] {
+                if seen.insert(next) {
+                    found.push(next);
+                    stack.push(next);
+                }
+            }
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use autodomd_library_common::TaskCategory;
+
+    fn task(id: &str, effort: &str, dependencies: &[&str], status: TaskStatus) -> Task {
+        let mut task = Task::from_markdown(
+            id.to_string(),
+            TaskCategory::General,
+            format!("todo/{}.md", id).into(),
+        );
+        task.id = id.to_string();
+        task.status = status;
+        task.metadata.effort = Some(effort.to_string());
+        task.metadata.dependencies = dependencies.iter().map(|d| d.to_string()).collect();
+        task
+    }
+
+    fn effort(task: &Task) -> f64 {
+        task.metadata
+            .effort
+            .as_deref()
+            .and_then(|e| e.parse().ok())
+            .unwrap_or(1.0)
+    }
+
+    fn ids(tasks: &[&Task]) -> Vec<String> {
+        tasks.iter().map(|task| task.id.clone()).collect()
+    }
+
+    #[test]
+    fn test_plan() {
+        // setup (done) -> core -> api -> release; core -> docs (long) -> release
+        let tasks = vec![
+            task("setup", "5", &[], TaskStatus::Done),
+            task("core", "2", &["setup"], TaskStatus::Open),
+            task("api", "1", &["core"], TaskStatus::Open),
+            task("docs", "4", &["core"], TaskStatus::Open),
+            task("release", "1", &["api", "docs"], TaskStatus::Open),
+            task("spike", "1", &[], TaskStatus::Blocked),
+            task("cleanup", "1", &["setup"], TaskStatus::InProgress),
+        ];
+        let plan = TaskGraph::build(&tasks).plan(effort);
+
+        assert_eq!(ids(&plan.critical_path), vec!["core", "docs", "release"]);
+        assert_eq!(plan.critical_path_effort, 7.0);
+        assert_eq!(ids(&plan.ready), vec!["core"]);
+
+        let unblocks: Vec<(&str, usize, f64)> = plan
+            .unblocks
+            .iter()
+            .map(|u| (u.task.id.as_str(), u.downstream_tasks, u.downstream_effort))
+            .collect();
+        assert_eq!(
+            unblocks,
+            vec![("core", 3, 6.0), ("api", 1, 1.0), ("docs", 1, 1.0)]
+        );
+    }
+
+    #[test]
+    fn test_plan_survives_cycles() {
+        let tasks = vec![
+            task("a", "1", &["b"], TaskStatus::Open),
+            task("b", "1", &["a"], TaskStatus::Open),
+        ];
+        let plan = TaskGraph::build(&tasks).plan(effort);
+
+        assert_eq!(plan.critical_path.len(), 1);
+        assert!(plan.ready.is_empty());
+    }
+}
@@ -0,0 +1,416 @@
+//! Language registry for autodomd
+//!
+//! This microcrate defines the comment syntax of every supported language in one
+//! place. The scanner uses it to decide which files to read and the parser uses it
+//! to find comments, so adding a language here is all it takes to support it.
+
+mod language_builtins;
+
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use autodomd_library_common::{TodoError, TodoResult};
+
+use language_builtins::builtin_languages;
+
+/// Syntax definition for one language
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LanguageSyntax {
+    /// Human-readable language name
+    pub name: String,
+    /// File extensions without the leading dot (e.g. "rs")
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    /// Exact file names recognized regardless of extension (e.g. "Makefile")
+    #[serde(default)]
+    pub filenames: Vec<String>,
+    /// Interpreter names matched against a `#!` line (e.g. "bash", "python")
+    #[serde(default)]
+    pub interpreters: Vec<String>,
+    /// Markers that start a comment running to the end of the line
+    #[serde(default)]
+    pub line_comments: Vec<String>,
+    /// Start and end delimiters of block comments
+    #[serde(default)]
+    pub block_comments: Vec<(String, String)>,
+    /// Whether block comments nest, so `/* /* */ */` is one comment (Rust, Swift, Haskell)
+    #[serde(default)]
+    pub nested_block_comments: bool,
+    /// Delimiters that open and close string literals (backslash escapes apply)
+    #[serde(default)]
+    pub string_delimiters: Vec<String>,
+    /// Delimiters of raw string literals in which backslash escapes do not apply
+    #[serde(default)]
+    pub raw_string_delimiters: Vec<String>,
+    /// Prefixed raw string syntax such as Rust's `r#"..."#`
+    #[serde(default)]
+    pub raw_strings: Option<RawStringStyle>,
+    /// Whether `'` may start either a character literal or a lifetime/label (Rust)
+    #[serde(default)]
+    pub char_literals: bool,
+    /// Whether single-character string delimiters must close on the same line
+    /// (keeps apostrophes in unquoted YAML or HTML text from swallowing comments)
+    #[serde(default)]
+    pub single_line_strings: bool,
+    /// Whether a line comment marker only counts at the start of a line or after
+    /// whitespace (shell `$#` and YAML `a#b` are not comments)
+    #[serde(default)]
+    pub comments_after_whitespace: bool,
+}
+
+/// Prefixed raw string literal syntax
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RawStringStyle {
+    /// Rust: `r"..."`, `r#"..."#`, `br"..."`
+    Rust,
+    /// C++: `R"delim(...)delim"`
+    Cpp,
+}
+
+impl LanguageSyntax {
+    /// Create an empty syntax definition for a language
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            extensions: Vec::new(),
+            filenames: Vec::new(),
+            interpreters: Vec::new(),
+            line_comments: Vec::new(),
+            block_comments: Vec::new(),
+            nested_block_comments: false,
+            string_delimiters: Vec::new(),
+            raw_string_delimiters: Vec::new(),
+            raw_strings: None,
+            char_literals: false,
+            single_line_strings: false,
+            comments_after_whitespace: false,
+        }
+    }
+
+    /// Add file extensions
+    pub fn with_extensions(mut self, extensions: &[&str]) -> Self {
+        self.extensions
+            .extend(extensions.iter().map(|e| e.to_string()));
+        self
+    }
+
+    /// Add exact file names
+    pub fn with_filenames(mut self, filenames: &[&str]) -> Self {
+        self.filenames
+            .extend(filenames.iter().map(|f| f.to_string()));
+        self
+    }
+
+    /// Add interpreter names for `#!` detection
+    pub fn with_interpreters(mut self, interpreters: &[&str]) -> Self {
+        self.interpreters
+            .extend(interpreters.iter().map(|i| i.to_string()));
+        self
+    }
+
+    /// Add line comment markers
+    pub fn with_line_comments(mut self, markers: &[&str]) -> Self {
+        self.line_comments
+            .extend(markers.iter().map(|m| m.to_string()));
+        self
+    }
+
+    /// Add a block comment delimiter pair
+    pub fn with_block_comment(mut self, start: &str, end: &str) -> Self {
+        self.block_comments
+            .push((start.to_string(), end.to_string()));
+        self
+    }
+
+    /// Let block comments nest
+    pub fn with_nested_block_comments(mut self) -> Self {
+        self.nested_block_comments = true;
+        self
+    }
+
+    /// Add string literal delimiters
+    pub fn with_string_delimiters(mut self, delimiters: &[&str]) -> Self {
+        self.string_delimiters
+            .extend(delimiters.iter().map(|d| d.to_string()));
+        self
+    }
+
+    /// Add raw string delimiters (no backslash escapes)
+    pub fn with_raw_string_delimiters(mut self, delimiters: &[&str]) -> Self {
+        self.raw_string_delimiters
+            .extend(delimiters.iter().map(|d| d.to_string()));
+        self
+    }
+
+    /// Set the prefixed raw string syntax
+    pub fn with_raw_strings(mut self, style: RawStringStyle) -> Self {
+        self.raw_strings = Some(style);
+        self
+    }
+
+    /// Treat `'` as a character literal or lifetime/label marker
+    pub fn with_char_literals(mut self) -> Self {
+        self.char_literals = true;
+        self
+    }
+
+    /// Require single-character string delimiters to close on the same line
+    pub fn with_single_line_strings(mut self) -> Self {
+        self.single_line_strings = true;
+        self
+    }
+
+    /// Only treat line comment markers as comments at line start or after whitespace
+    pub fn with_comments_after_whitespace(mut self) -> Self {
+        self.comments_after_whitespace = true;
+        self
+    }
+
+    /// Syntax used for files that no registered language claims
+    pub fn fallback() -> Self {
+        Self::new("Unknown")
+            .with_line_comments(&["//", "#"])
+            .with_block_comment("/*", "*/")
+            .with_string_delimiters(&["\"", "'"])
+    }
+
+    /// Whether this language claims the given path
+    pub fn matches_path(&self, path: &Path) -> bool {
+        let file_name = path.file_name().and_then(|n| n.to_str());
+        if file_name.is_some_and(|name| self.filenames.iter().any(|f| f == name)) {
+            return true;
+        }
+
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| self.extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+    }
+}
+
+/// Registry of languages whose files are scanned for TODO comments
+#[derive(Debug, Clone)]
+pub struct LanguageRegistry {
+    languages: Vec<LanguageSyntax>,
+}
+
+impl Default for LanguageRegistry {
+    fn default() -> Self {
+        Self {
+            languages: builtin_languages(),
+        }
+    }
+}
+
+/// Layout of a language definition file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LanguageFile {
+    #[serde(default, rename = "language")]
+    languages: Vec<LanguageSyntax>,
+}
+
+impl LanguageRegistry {
+    /// Create a registry with no languages
+    pub fn empty() -> Self {
+        Self {
+            languages: Vec::new(),
+        }
+    }
+
+    /// Register a language; later registrations take precedence for shared extensions
+    pub fn register(&mut self, language: LanguageSyntax) {
+        self.languages.push(language);
+    }
+
+    /// Register every `[[language]]` table from a TOML definition file
+    ///
+    /// ```toml
+    /// [[language]]
+    /// name = "Nix"
+    /// extensions = ["nix"]
+    /// line_comments = ["#"]
+    /// block_comments = [["/*", "*/"]]
+    /// string_delimiters = ["\""]
+    /// ```
+    ///
+    /// Optional keys: `filenames`, `interpreters`, `nested_block_comments`,
+    /// `raw_string_delimiters`, `raw_strings` (`"rust"` or `"cpp"`),
+    /// `char_literals`, `single_line_strings` and `comments_after_whitespace`.
+    pub fn register_file(&mut self, path: &Path) -> TodoResult<()> {
+        let content = fs::read_to_string(path)?;
+        self.register_toml(&content)
+            .map_err(|e| TodoError::Parse(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Register every `[[language]]` table from TOML content
+    pub fn register_toml(&mut self, content: &str) -> TodoResult<()> {
+        let file: LanguageFile =
+            toml::from_str(content).map_err(|e| TodoError::Parse(e.to_string()))?;
+        for language in file.languages {
+            self.register(language);
+        }
+        Ok(())
+    }
+
+    /// Find the language that claims a path
+    pub fn for_path(&self, path: &Path) -> Option<&LanguageSyntax> {
+        self.languages
+            .iter()
+            .rev()
+            .find(|language| language.matches_path(path))
+    }
+
+    /// Find the language whose interpreter is named on a `#!` line
+    pub fn for_shebang(&self, first_line: &str) -> Option<&LanguageSyntax> {
+        let interpreter = shebang_interpreter(first_line)?;
+        self.languages
+            .iter()
+            .rev()
+            .find(|language| language.interpreters.contains(&interpreter))
+    }
+
+    /// Find the language for a file by name or extension, falling back to its `#!` line
+    pub fn detect(&self, path: &Path, first_line: Option<&str>) -> Option<&LanguageSyntax> {
+        self.for_path(path)
+            .or_else(|| first_line.and_then(|line| self.for_shebang(line)))
+    }
+
+    /// Whether any registered language claims a path
+    pub fn is_supported(&self, path: &Path) -> bool {
+        self.for_path(path).is_some()
+    }
+
+    /// All registered languages in registration order
+    pub fn languages(&self) -> &[LanguageSyntax] {
+        &self.languages
+    }
+}
+
+/// Extract the interpreter name from a `#!` line, e.g. `#!/usr/bin/env python3` -> "python"
+fn shebang_interpreter(first_line: &str) -> Option<String> {
+    let command = first_line.strip_prefix("#!")?.trim();
+    let mut words = command.split_whitespace();
+    let mut program = words.next()?.rsplit('/').next()?;
+
+    // `/usr/bin/env [-S] program`
+    if program == "env" {
+        program = words.find(|word| !word.starts_with('-'))?;
+    }
+
+    // Drop version suffixes: python3.11 -> python
+    let name = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_lookup() {
+        let registry = LanguageRegistry::default();
+
+        assert_eq!(
+            registry
+                .for_path(Path::new("src/main.rs"))
+                .map(|l| l.name.as_str()),
+            Some("Rust")
+        );
+        assert_eq!(
+            registry
+                .for_path(Path::new("include/a.HPP"))
+                .map(|l| l.name.as_str()),
+            Some("C++")
+        );
+        assert!(!registry.is_supported(Path::new("README.md")));
+    }
+
+    #[test]
+    fn test_register_from_code_overrides_builtin() {
+        let mut registry = LanguageRegistry::default();
+        registry.register(
+            LanguageSyntax::new("Rust (custom)")
+                .with_extensions(&["rs"])
+                .with_line_comments(&["//"]),
+        );
+
+        assert_eq!(
+            registry
+                .for_path(Path::new("lib.rs"))
+                .map(|l| l.name.as_str()),
+            Some("Rust (custom)")
+        );
+    }
+
+    #[test]
+    fn test_register_from_toml() {
+        let mut registry = LanguageRegistry::empty();
+        registry
+            .register_toml(
+                r##"
+                [[language]]
+                name = "Nix"
+                extensions = ["nix"]
+                filenames = ["flake.lock"]
+                line_comments = ["#"]
+                block_comments = [["/*", "*/"]]
+                "##,
+            )
+            .unwrap();
+
+        let nix = registry.for_path(Path::new("default.nix")).unwrap();
+        assert_eq!(nix.line_comments, vec!["#"]);
+        assert_eq!(
+            nix.block_comments,
+            vec![("/*".to_string(), "*/".to_string())]
+        );
+        assert!(registry.is_supported(Path::new("flake.lock")));
+    }
+
+    #[test]
+    fn test_register_from_toml_rejects_unknown_keys() {
+        let mut registry = LanguageRegistry::empty();
+        assert!(registry
+            .register_toml("[[language]]\nname = \"X\"\ncomment = \"#\"\n")
+            .is_err());
+    }
+
+    #[test]
+    fn test_filename_and_shebang_detection() {
+        let registry = LanguageRegistry::default();
+        let name = |language: Option<&LanguageSyntax>| language.map(|l| l.name.clone());
+
+        assert_eq!(
+            name(registry.for_path(Path::new("build/Makefile"))),
+            Some("Makefile".to_string())
+        );
+        assert_eq!(
+            name(registry.for_path(Path::new("Dockerfile"))),
+            Some("Dockerfile".to_string())
+        );
+        assert_eq!(
+            name(registry.for_path(Path::new("Justfile"))),
+            Some("Just".to_string())
+        );
+        assert_eq!(
+            name(registry.for_path(Path::new("CMakeLists.txt"))),
+            Some("CMake".to_string())
+        );
+        assert_eq!(
+            name(registry.detect(Path::new("bin/deploy"), Some("#!/bin/bash"))),
+            Some("Shell".to_string())
+        );
+        assert_eq!(
+            name(registry.detect(Path::new("tool"), Some("#!/usr/bin/env -S python3.11 -u"))),
+            Some("Python".to_string())
+        );
+        assert_eq!(
+            name(registry.detect(Path::new("notes"), Some("plain text"))),
+            None
+        );
+    }
+}
@@ -0,0 +1,256 @@
+//! Built-in language definitions
+//!
+//! Grouped by comment style. Later entries win when two languages claim the
+//! same extension, so keep more specific definitions towards the end.
+
+use crate::{LanguageSyntax, RawStringStyle};
+
+/// String delimiters for C-family languages (`'` is a character literal)
+const C_STRINGS: &[&str] = &["\"", "'"];
+
+/// Built-in language definitions
+pub(crate) fn builtin_languages() -> Vec<LanguageSyntax> {
+    let mut languages = Vec::new();
+    languages.extend(c_family_languages());
+    languages.extend(hash_comment_languages());
+    languages.extend(build_and_config_languages());
+    languages.extend(other_languages());
+    languages.extend(markup_languages());
+    languages
+}
+
+/// Languages with `//` and `/* */` comments
+fn c_family_languages() -> Vec<LanguageSyntax> {
+    let c_family = |name: &str| {
+        LanguageSyntax::new(name)
+            .with_line_comments(&["//"])
+            .with_block_comment("/*", "*/")
+    };
+
+    vec![
+        c_family("Rust")
+            .with_extensions(&["rs"])
+            .with_string_delimiters(&["\""])
+            .with_raw_strings(RawStringStyle::Rust)
+            .with_char_literals()
+            .with_nested_block_comments(),
+        c_family("JavaScript")
+            .with_extensions(&["js", "jsx", "mjs", "cjs"])
+            .with_interpreters(&["node", "nodejs"])
+            .with_string_delimiters(&["\"", "'", "`"]),
+        c_family("TypeScript")
+            .with_extensions(&["ts", "tsx", "mts", "cts"])
+            .with_interpreters(&["deno", "ts-node"])
+            .with_string_delimiters(&["\"", "'", "`"]),
+        c_family("Java")
+            .with_extensions(&["java"])
+            .with_string_delimiters(C_STRINGS),
+        c_family("C")
+            .with_extensions(&["c", "h"])
+            .with_string_delimiters(C_STRINGS),
+        c_family("C++")
+            .with_extensions(&["cpp", "cc", "cxx", "hpp", "hh", "hxx"])
+            .with_string_delimiters(C_STRINGS)
+            .with_raw_strings(RawStringStyle::Cpp),
+        c_family("Go")
+            .with_extensions(&["go"])
+            .with_string_delimiters(&["\"", "'"])
+            .with_raw_string_delimiters(&["`"]),
+        c_family("PHP")
+            .with_extensions(&["php"])
+            .with_interpreters(&["php"])
+            .with_line_comments(&["#"])
+            .with_string_delimiters(&["\"", "'"]),
+        c_family("Swift")
+            .with_extensions(&["swift"])
+            .with_string_delimiters(&["\"\"\"", "\""])
+            .with_nested_block_comments(),
+        c_family("Kotlin")
+            .with_extensions(&["kt", "kts"])
+            .with_string_delimiters(&["\"\"\"", "\"", "'"])
+            .with_nested_block_comments(),
+        c_family("Scala")
+            .with_extensions(&["scala", "sc"])
+            .with_string_delimiters(&["\"\"\"", "\"", "'"])
+            .with_nested_block_comments(),
+        c_family("Dart")
+            .with_extensions(&["dart"])
+            .with_string_delimiters(&["\"\"\"", "'''", "\"", "'"])
+            .with_nested_block_comments(),
+        LanguageSyntax::new("Zig")
+            .with_extensions(&["zig"])
+            .with_line_comments(&["//"])
+            .with_string_delimiters(C_STRINGS),
+        LanguageSyntax::new("CSS")
+            .with_extensions(&["css"])
+            .with_block_comment("/*", "*/")
+            .with_string_delimiters(&["\"", "'"])
+            .with_single_line_strings(),
+        c_family("SCSS")
+            .with_extensions(&["scss", "less"])
+            .with_string_delimiters(&["\"", "'"])
+            .with_single_line_strings(),
+    ]
+}
+
+/// Scripting languages with `#` comments
+fn hash_comment_languages() -> Vec<LanguageSyntax> {
+    let hash = |name: &str| LanguageSyntax::new(name).with_line_comments(&["#"]);
+
+    vec![
+        hash("Python")
+            .with_extensions(&["py", "pyi"])
+            .with_interpreters(&["python"])
+            .with_string_delimiters(&["\"\"\"", "'''", "\"", "'"]),
+        hash("Ruby")
+            .with_extensions(&["rb", "rake", "gemspec"])
+            .with_filenames(&["Rakefile", "Gemfile"])
+            .with_interpreters(&["ruby"])
+            .with_string_delimiters(&["\"", "'"]),
+        hash("Shell")
+            .with_extensions(&["sh", "bash", "zsh", "ksh"])
+            .with_filenames(&[".bashrc", ".bash_profile", ".zshrc", ".profile"])
+            .with_interpreters(&["sh", "bash", "zsh", "ksh", "dash"])
+            .with_string_delimiters(&["\""])
+            .with_raw_string_delimiters(&["'"])
+            .with_comments_after_whitespace(),
+        hash("PowerShell")
+            .with_extensions(&["ps1", "psm1", "psd1"])
+            .with_interpreters(&["pwsh", "powershell"])
+            .with_block_comment("<#", "#>")
+            .with_string_delimiters(&["\""])
+            .with_raw_string_delimiters(&["'"]),
+        hash("Elixir")
+            .with_extensions(&["ex", "exs"])
+            .with_interpreters(&["elixir"])
+            .with_string_delimiters(&["\"\"\"", "\""]),
+        hash("R")
+            .with_extensions(&["r", "R"])
+            .with_interpreters(&["Rscript"])
+            .with_string_delimiters(&["\"", "'"]),
+        hash("Julia")
+            .with_extensions(&["jl"])
+            .with_interpreters(&["julia"])
+            .with_block_comment("#=", "=#")
+            .with_string_delimiters(&["\"\"\"", "\""])
+            .with_nested_block_comments(),
+        hash("Nim")
+            .with_extensions(&["nim", "nims", "nimble"])
+            .with_block_comment("#[", "]#")
+            .with_string_delimiters(&["\"\"\"", "\"", "'"])
+            .with_nested_block_comments(),
+        hash("Perl")
+            .with_extensions(&["pl", "pm"])
+            .with_interpreters(&["perl"])
+            .with_string_delimiters(&["\"", "'"])
+            .with_comments_after_whitespace(),
+    ]
+}
+
+/// Build, infrastructure and configuration files
+fn build_and_config_languages() -> Vec<LanguageSyntax> {
+    let hash = |name: &str| LanguageSyntax::new(name).with_line_comments(&["#"]);
+
+    vec![
+        hash("YAML")
+            .with_extensions(&["yml", "yaml"])
+            .with_string_delimiters(&["\"", "'"])
+            .with_single_line_strings()
+            .with_comments_after_whitespace(),
+        hash("TOML")
+            .with_extensions(&["toml"])
+            .with_filenames(&["Cargo.lock"])
+            .with_string_delimiters(&["\"\"\"", "\""])
+            .with_raw_string_delimiters(&["'''", "'"])
+            .with_single_line_strings(),
+        hash("Dockerfile")
+            .with_extensions(&["dockerfile"])
+            .with_filenames(&["Dockerfile", "Containerfile"])
+            .with_string_delimiters(&["\"", "'"])
+            .with_single_line_strings()
+            .with_comments_after_whitespace(),
+        hash("Makefile")
+            .with_extensions(&["mk", "mak"])
+            .with_filenames(&["Makefile", "makefile", "GNUmakefile"])
+            .with_interpreters(&["make"]),
+        hash("Just")
+            .with_extensions(&["just"])
+            .with_filenames(&["Justfile", "justfile", ".justfile"])
+            .with_string_delimiters(&["\"", "'"])
+            .with_single_line_strings(),
+        hash("CMake")
+            .with_extensions(&["cmake"])
+            .with_filenames(&["CMakeLists.txt"])
+            .with_block_comment("#[[", "]]")
+            .with_string_delimiters(&["\""]),
+        LanguageSyntax::new("Terraform")
+            .with_extensions(&["tf", "tfvars", "hcl"])
+            .with_line_comments(&["#", "//"])
+            .with_block_comment("/*", "*/")
+            .with_string_delimiters(&["\""]),
+    ]
+}
+
+/// Languages with less common comment markers
+fn other_languages() -> Vec<LanguageSyntax> {
+    vec![
+        LanguageSyntax::new("SQL")
+            .with_extensions(&["sql"])
+            .with_line_comments(&["--"])
+            .with_block_comment("/*", "*/")
+            .with_string_delimiters(&["'", "\""]),
+        LanguageSyntax::new("Lua")
+            .with_extensions(&["lua"])
+            .with_interpreters(&["lua", "luajit"])
+            .with_block_comment("--[[", "]]")
+            .with_line_comments(&["--"])
+            .with_string_delimiters(&["\"", "'"]),
+        LanguageSyntax::new("Haskell")
+            .with_extensions(&["hs", "lhs"])
+            .with_interpreters(&["runhaskell", "stack"])
+            .with_line_comments(&["--"])
+            .with_block_comment("{-", "-}")
+            .with_string_delimiters(&["\""])
+            .with_nested_block_comments(),
+        LanguageSyntax::new("Erlang")
+            .with_extensions(&["erl", "hrl"])
+            .with_interpreters(&["escript"])
+            .with_line_comments(&["%"])
+            .with_string_delimiters(&["\""]),
+        LanguageSyntax::new("Lisp")
+            .with_extensions(&["lisp", "lsp", "el", "scm", "ss", "rkt"])
+            .with_interpreters(&["sbcl", "racket", "guile"])
+            .with_line_comments(&[";"])
+            .with_block_comment("#|", "|#")
+            .with_string_delimiters(&["\""])
+            .with_nested_block_comments(),
+        LanguageSyntax::new("Clojure")
+            .with_extensions(&["clj", "cljs", "cljc", "edn"])
+            .with_line_comments(&[";"])
+            .with_string_delimiters(&["\""]),
+    ]
+}
+
+/// Markup languages with `<!-- -->` comments
+fn markup_languages() -> Vec<LanguageSyntax> {
+    let markup = |name: &str| {
+        LanguageSyntax::new(name)
+            .with_block_comment("<!--", "-->")
+            .with_string_delimiters(&["\"", "'"])
+            .with_single_line_strings()
+    };
+
+    vec![
+        markup("HTML").with_extensions(&["html", "htm", "xhtml"]),
+        markup("XML").with_extensions(&["xml", "xsd", "xsl", "xslt", "svg"]),
+        // Single-file components mix markup with script and style blocks
+        markup("Vue")
+            .with_extensions(&["vue"])
+            .with_line_comments(&["//"])
+            .with_block_comment("/*", "*/"),
+        markup("Svelte")
+            .with_extensions(&["svelte"])
+            .with_line_comments(&["//"])
+            .with_block_comment("/*", "*/"),
+    ]
+}
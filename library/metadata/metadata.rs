@@ -0,0 +1,272 @@
+//! Task-file metadata parsing for autodomd
+//!
+//! This microcrate splits a markdown task file into its metadata block and
+//! body, and parses the metadata into a typed [`TaskMetadata`]. Three forms are
+//! understood:
+//!
+//! - YAML frontmatter between `---` lines at the top of the file
+//! - TOML frontmatter between `+++` lines at the top of the file
+//! - a fenced ```` ```yaml ```` block anywhere in the file (the original format)
+//!
+//! Effort estimates are kept as written and read with [`Effort`] and
+//! [`EffortScale`], which converts sizes, points and durations into hours.
+
+mod metadata_effort;
+mod metadata_values;
+
+use chrono::NaiveDate;
+
+use autodomd_library_common::{TaskMetadata, TaskPriority, TaskStatus, TodoError, TodoResult};
+
+use metadata_values::{metadata_from_value, toml_to_yaml};
+
+pub use metadata_effort::{Effort, EffortScale, EffortTotal};
+
+/// Format of a metadata block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetadataFormat {
+    Yaml,
+    Toml,
+}
+
+/// Metadata keys that set fields of the task itself rather than [`TaskMetadata`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskFields {
+    /// Explicit task ID from the `id` key
+    pub id: Option<String>,
+    /// Priority from the `priority` key
+    pub priority: Option<TaskPriority>,
+    /// Workflow status from the `status` key
+    pub status: Option<TaskStatus>,
+    /// Why the task is blocked, from the `blocked_reason` key
+    pub blocked_reason: Option<String>,
+    /// Issue-tracker references from the `issue` / `issues` keys
+    pub issues: Vec<String>,
+    /// Due date from the `due` key
+    pub due_date: Option<NaiveDate>,
+}
+
+/// A task file split into its parsed metadata and the remaining markdown
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Frontmatter {
+    /// Parsed metadata (empty when the file has none)
+    pub metadata: TaskMetadata,
+    /// Keys that set task fields (all unset when the file has none)
+    pub fields: TaskFields,
+    /// Markdown content with the metadata block removed
+    pub body: String,
+    /// Keys that were ignored because their value is invalid, one message each
+    pub problems: Vec<String>,
+}
+
+/// Split a task file into metadata and body, parsing the metadata
+pub fn parse_frontmatter(content: &str) -> TodoResult<Frontmatter> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+
+    match split_metadata_block(content)? {
+        Some((format, raw, body)) => {
+            let (metadata, fields, problems) = parse_metadata(raw, format)?;
+            Ok(Frontmatter {
+                metadata,
+                fields,
+                body,
+                problems,
+            })
+        }
+        None => Ok(Frontmatter {
+            body: content.to_string(),
+            ..Frontmatter::default()
+        }),
+    }
+}
+
+/// Parse the text of a metadata block, returning the metadata, the task fields
+/// and a message for each key whose value is invalid
+pub fn parse_metadata(
+    raw: &str,
+    format: MetadataFormat,
+) -> TodoResult<(TaskMetadata, TaskFields, Vec<String>)> {
+    let value = match format {
+        MetadataFormat::Yaml => serde_norway::from_str(raw)
+            .map_err(|e| TodoError::Parse(format!("invalid YAML frontmatter: {}", e)))?,
+        MetadataFormat::Toml => {
+            let table: toml::Table = toml::from_str(raw)
+                .map_err(|e| TodoError::Parse(format!("invalid TOML frontmatter: {}", e)))?;
+            toml_to_yaml(toml::Value::Table(table))
+        }
+    };
+
+    metadata_from_value(value)
+}
+
+/// Find the metadata block, returning its format, raw text and the body without it
+fn split_metadata_block(content: &str) -> TodoResult<Option<(MetadataFormat, &str, String)>> {
+    let first_line = content.split_inclusive('\n').next().unwrap_or("");
+
+    for (delimiter, format) in [("---", MetadataFormat::Yaml), ("+++", MetadataFormat::Toml)] {
+        if first_line.trim_end() != delimiter {
+            continue;
+        }
+
+        let (raw, after) =
+            find_closing_line(&content[first_line.len()..], |line| line == delimiter).ok_or_else(
+                || {
+                    TodoError::Parse(format!(
+                        "frontmatter opened with `{}` is never closed",
+                        delimiter
+                    ))
+                },
+            )?;
+        return Ok(Some((format, raw, after.to_string())));
+    }
+
+    Ok(find_fenced_yaml(content).map(|(raw, body)| (MetadataFormat::Yaml, raw, body)))
+}
+
+/// Find a fenced YAML block, the format task files used before frontmatter
+fn find_fenced_yaml(content: &str) -> Option<(&str, String)> {
+    let mut offset = 0;
+    let mut lines = content.split_inclusive('\n').peekable();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+        let untagged_metadata = trimmed == "```"
+            && lines
+                .peek()
+                .is_some_and(|next| next.contains("priority:") || next.contains("dependencies:"));
+
+        if trimmed == "```yaml" || untagged_metadata {
+            let block_start = offset + line.len();
+            let (raw, after) = find_closing_line(&content[block_start..], |line| line == "```")?;
+            return Some((raw, format!("{}{}", &content[..offset], after)));
+        }
+        offset += line.len();
+    }
+
+    None
+}
+
+/// Split text at the first line accepted by `is_closing`, returning the text
+/// before that line and the text after it
+fn find_closing_line(text: &str, is_closing: impl Fn(&str) -> bool) -> Option<(&str, &str)> {
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        if is_closing(line.trim()) {
+            return Some((&text[..offset], &text[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yaml_frontmatter() {
+        let content = concat!(
+            "---\n",
+            "priority: high\n",
+            "dependencies: [core-scanning, cli]\n",
+            "blocks: release\n",
+            "effort: 3d\n",
+            "status: in-progress\n",
+            "tags: [backend]\n",
+            "issues: [\"#12\", PROJ-7]\n",
+            "due: 2026-12-01\n",
+            "reviewer: alice\n",
+            "---\n",
+            "# Title\n",
+        );
+        let frontmatter = parse_frontmatter(content).unwrap();
+        let metadata = frontmatter.metadata;
+        let fields = frontmatter.fields;
+
+        assert_eq!(fields.priority, Some(TaskPriority::High));
+        assert_eq!(metadata.dependencies, vec!["core-scanning", "cli"]);
+        assert_eq!(metadata.blocks, vec!["release"]);
+        assert_eq!(metadata.effort.as_deref(), Some("3d"));
+        assert_eq!(fields.status, Some(TaskStatus::InProgress));
+        assert_eq!(metadata.tags, vec!["backend"]);
+        assert_eq!(fields.issues, vec!["#12", "PROJ-7"]);
+        assert_eq!(
+            fields.due_date,
+            chrono::NaiveDate::from_ymd_opt(2026, 12, 1)
+        );
+        assert_eq!(
+            metadata.custom.get("reviewer").map(String::as_str),
+            Some("alice")
+        );
+        assert_eq!(frontmatter.body, "# Title\n");
+    }
+
+    #[test]
+    fn test_toml_frontmatter() {
+        let content = "+++\npriority = \"low\"\nblocks = [\"docs\"]\ndue = 2026-03-15\nestimate = 5\n+++\n# Title\n";
+        let frontmatter = parse_frontmatter(content).unwrap();
+
+        assert_eq!(frontmatter.fields.priority, Some(TaskPriority::Low));
+        assert_eq!(frontmatter.metadata.blocks, vec!["docs"]);
+        assert_eq!(
+            frontmatter.fields.due_date,
+            chrono::NaiveDate::from_ymd_opt(2026, 3, 15)
+        );
+        assert_eq!(
+            frontmatter
+                .metadata
+                .custom
+                .get("estimate")
+                .map(String::as_str),
+            Some("5")
+        );
+        assert_eq!(frontmatter.body, "# Title\n");
+    }
+
+    #[test]
+    fn test_fenced_yaml_block() {
+        let content = "# Title\n\n```yaml\npriority: medium\nestimated_effort: large\n```\n\n## Overview\nText.\n";
+        let frontmatter = parse_frontmatter(content).unwrap();
+
+        assert_eq!(frontmatter.fields.priority, Some(TaskPriority::Medium));
+        assert_eq!(frontmatter.metadata.effort.as_deref(), Some("large"));
+        assert_eq!(frontmatter.body, "# Title\n\n\n## Overview\nText.\n");
+    }
+
+    #[test]
+    fn test_without_metadata() {
+        let frontmatter = parse_frontmatter("# Title\n\n---\n\nText\n").unwrap();
+
+        assert_eq!(frontmatter.metadata, TaskMetadata::default());
+        assert_eq!(frontmatter.fields, TaskFields::default());
+        assert_eq!(frontmatter.body, "# Title\n\n---\n\nText\n");
+    }
+
+    #[test]
+    fn test_malformed_metadata_is_an_error() {
+        let error = |content: &str| parse_frontmatter(content).unwrap_err().to_string();
+
+        assert!(error("---\npriority: [high\n---\n").contains("invalid YAML frontmatter"));
+        assert!(error("+++\npriority = high\n+++\n").contains("invalid TOML frontmatter"));
+        assert!(error("---\npriority: high\n").contains("never closed"));
+        assert!(error("---\n- just\n- a list\n---\n").contains("key: value"));
+    }
+
+    #[test]
+    fn test_invalid_values_are_reported() {
+        let content =
+            "---\npriority: urgent\nstatus: [a, b]\ndue: next week\neffort: 3d\n---\n# Title\n";
+        let frontmatter = parse_frontmatter(content).unwrap();
+
+        assert_eq!(frontmatter.fields, TaskFields::default());
+        assert_eq!(frontmatter.metadata.effort.as_deref(), Some("3d"));
+        assert_eq!(
+            frontmatter.problems,
+            vec![
+                "invalid priority `urgent` (expected high, medium or low)",
+                "`status` must be a single value",
+                "invalid due date `next week` (expected YYYY-MM-DD)",
+            ]
+        );
+    }
+}
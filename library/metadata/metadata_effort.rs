@@ -0,0 +1,223 @@
+//! Effort estimates and the conversion between their units
+//!
+//! An `effort` (or `estimated_effort`) value is one of:
+//!
+//! - a t-shirt size: `xs`, `s`, `m`, `l`, `xl`, `xxl`, with `small`, `medium`,
+//!   `large` and the older `low` / `high` as aliases
+//! - story points: `5`, `5pt`, `5 points`
+//! - a duration: `30m`, `8h`, `3d`, `2w`
+//!
+//! Anything in parentheses is a note and ignored, so `high (new dependency)`
+//! is a large task. An [`EffortScale`] turns all three kinds into hours so
+//! estimates can be added up.
+
+use autodomd_library_common::{Task, TaskStatus};
+
+/// A parsed effort estimate
+#[derive(Debug, Clone, PartialEq)]
+pub enum Effort {
+    /// T-shirt size, lowercased with aliases resolved (`m`, `xl`)
+    Size(String),
+    /// Story points
+    Points(f64),
+    /// Hours of work (minutes are converted to hours)
+    Hours(f64),
+    /// Working days, converted with the scale's hours per day
+    Days(f64),
+    /// Working weeks, converted with the scale's days per week
+    Weeks(f64),
+}
+
+impl Effort {
+    /// Parse an estimate, returning `None` when it is empty or not understood
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.split('(').next().unwrap_or("").trim().to_lowercase();
+        if text.is_empty() {
+            return None;
+        }
+
+        let number_len = text
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(text.len());
+        if number_len == 0 {
+            return size_name(&text).map(Effort::Size);
+        }
+
+        let amount: f64 = text[..number_len].parse().ok()?;
+        match text[number_len..].trim() {
+            "" | "pt" | "pts" | "sp" | "point" | "points" => Some(Effort::Points(amount)),
+            "m" | "min" | "mins" | "minute" | "minutes" => Some(Effort::Hours(amount / 60.0)),
+            "h" | "hr" | "hrs" | "hour" | "hours" => Some(Effort::Hours(amount)),
+            "d" | "day" | "days" => Some(Effort::Days(amount)),
+            "w" | "wk" | "week" | "weeks" => Some(Effort::Weeks(amount)),
+            _ => None,
+        }
+    }
+}
+
+/// Canonical t-shirt size for a word, or the word itself for custom sizes
+fn size_name(word: &str) -> Option<String> {
+    if !word.chars().all(|c| c.is_ascii_alphabetic() || c == '-') {
+        return None;
+    }
+
+    let name = match word {
+        "extra-small" | "x-small" => "xs",
+        "small" | "low" => "s",
+        "medium" => "m",
+        "large" | "high" => "l",
+        "extra-large" | "x-large" => "xl",
+        "xx-large" => "xxl",
+        other => other,
+    };
+    Some(name.to_string())
+}
+
+/// Conversion between effort units, all expressed in hours
+#[derive(Debug, Clone, PartialEq)]
+pub struct EffortScale {
+    /// Working hours in a day
+    pub hours_per_day: f64,
+    /// Working days in a week
+    pub days_per_week: f64,
+    /// Hours of work in one story point
+    pub hours_per_point: f64,
+    /// Story points for each t-shirt size, by lowercase name
+    pub sizes: Vec<(String, f64)>,
+}
+
+impl Default for EffortScale {
+    fn default() -> Self {
+        Self {
+            hours_per_day: 8.0,
+            days_per_week: 5.0,
+            hours_per_point: 4.0,
+            sizes: [
+                ("xs", 1.0),
+                ("s", 2.0),
+                ("m", 3.0),
+                ("l", 5.0),
+                ("xl", 8.0),
+                ("xxl", 13.0),
+            ]
+            .into_iter()
+            .map(|(name, points)| (name.to_string(), points))
+            .collect(),
+        }
+    }
+}
+
+impl EffortScale {
+    /// Set the story points of a t-shirt size, adding it if it is new
+    pub fn set_size(&mut self, name: &str, points: f64) {
+        let Some(name) = size_name(&name.to_lowercase()) else {
+            return;
+        };
+        match self.sizes.iter_mut().find(|(size, _)| *size == name) {
+            Some(size) => size.1 = points,
+            None => self.sizes.push((name, points)),
+        }
+    }
+
+    /// Hours of work in an estimate, or `None` for an unknown t-shirt size
+    pub fn hours(&self, effort: &Effort) -> Option<f64> {
+        match effort {
+            Effort::Size(name) => self
+                .sizes
+                .iter()
+                .find(|(size, _)| size == name)
+                .map(|(_, points)| points * self.hours_per_point),
+            Effort::Points(points) => Some(points * self.hours_per_point),
+            Effort::Hours(hours) => Some(*hours),
+            Effort::Days(days) => Some(days * self.hours_per_day),
+            Effort::Weeks(weeks) => Some(weeks * self.days_per_week * self.hours_per_day),
+        }
+    }
+
+    /// Hours of work in a task's estimate, if it has one that can be read
+    pub fn task_hours(&self, task: &Task) -> Option<f64> {
+        let effort = Effort::parse(task.metadata.effort.as_deref()?)?;
+        self.hours(&effort)
+    }
+
+    /// Add up the estimates of every task that is not done
+    pub fn remaining<'a>(&self, tasks: impl IntoIterator<Item = &'a Task>) -> EffortTotal {
+        let mut total = EffortTotal::default();
+        for task in tasks
+            .into_iter()
+            .filter(|task| task.status != TaskStatus::Done)
+        {
+            match self.task_hours(task) {
+                Some(hours) => {
+                    total.hours += hours;
+                    total.estimated += 1;
+                }
+                None => total.unestimated += 1,
+            }
+        }
+        total
+    }
+
+    /// Render hours as days once they reach a full day (`6h`, `2.5d`)
+    pub fn format(&self, hours: f64) -> String {
+        if hours >= self.hours_per_day && self.hours_per_day > 0.0 {
+            format!("{}d", round_tenths(hours / self.hours_per_day))
+        } else {
+            format!("{}h", round_tenths(hours))
+        }
+    }
+}
+
+/// Sum of the estimates for a set of tasks
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EffortTotal {
+    /// Total estimated hours
+    pub hours: f64,
+    /// Tasks with a readable estimate
+    pub estimated: usize,
+    /// Tasks without one
+    pub unestimated: usize,
+}
+
+/// Round to one decimal place
+fn round_tenths(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_effort() {
+        assert_eq!(Effort::parse("M"), Some(Effort::Size("m".to_string())));
+        assert_eq!(
+            Effort::parse("high (new library dependency needed)"),
+            Some(Effort::Size("l".to_string()))
+        );
+        assert_eq!(Effort::parse("5"), Some(Effort::Points(5.0)));
+        assert_eq!(Effort::parse("3 points"), Some(Effort::Points(3.0)));
+        assert_eq!(Effort::parse("1.5d"), Some(Effort::Days(1.5)));
+        assert_eq!(Effort::parse("8h"), Some(Effort::Hours(8.0)));
+        assert_eq!(Effort::parse("30m"), Some(Effort::Hours(0.5)));
+        assert_eq!(Effort::parse("2 weeks"), Some(Effort::Weeks(2.0)));
+        assert_eq!(Effort::parse("3 parsecs"), None);
+        assert_eq!(Effort::parse(""), None);
+    }
+
+    #[test]
+    fn test_effort_scale() {
+        let mut scale = EffortScale::default();
+        assert_eq!(scale.hours(&Effort::Size("m".to_string())), Some(12.0));
+        assert_eq!(scale.hours(&Effort::Weeks(1.0)), Some(40.0));
+        assert_eq!(scale.hours(&Effort::Size("huge".to_string())), None);
+
+        scale.hours_per_day = 6.0;
+        scale.set_size("Medium", 1.0);
+        scale.set_size("huge", 20.0);
+        assert_eq!(scale.hours(&Effort::Size("m".to_string())), Some(4.0));
+        assert_eq!(scale.hours(&Effort::Size("huge".to_string())), Some(80.0));
+        assert_eq!(scale.format(4.0), "4h");
+        assert_eq!(scale.format(15.0), "2.5d");
+    }
+}
@@ -0,0 +1,163 @@
+//! Conversion of parsed metadata values into [`TaskMetadata`] and [`TaskFields`]
+//!
+//! YAML and TOML are both parsed into a `serde_norway::Value` so the known keys
+//! are interpreted in one place, whichever format the file used.
+
+use chrono::NaiveDate;
+use serde_norway::Value;
+
+use autodomd_library_common::{TaskMetadata, TaskPriority, TaskStatus, TodoError, TodoResult};
+
+use crate::TaskFields;
+
+/// Interpret a parsed metadata mapping
+///
+/// A known key with an invalid value is left unset and described in the
+/// returned problems, so the rest of the task is still usable.
+pub(crate) fn metadata_from_value(
+    value: Value,
+) -> TodoResult<(TaskMetadata, TaskFields, Vec<String>)> {
+    let mapping = match value {
+        Value::Null => return Ok(Default::default()),
+        Value::Mapping(mapping) => mapping,
+        _ => return Err(invalid("frontmatter must be a list of `key: value` pairs")),
+    };
+
+    let mut metadata = TaskMetadata::default();
+    let mut fields = TaskFields::default();
+    let mut problems = Vec::new();
+    for (key, value) in mapping {
+        let key = key
+            .as_str()
+            .ok_or_else(|| invalid("frontmatter keys must be strings"))?
+            .to_string();
+
+        if let Err(problem) = apply_field(&mut metadata, &mut fields, key, &value) {
+            problems.push(problem);
+        }
+    }
+
+    Ok((metadata, fields, problems))
+}
+
+/// Store one metadata key, or describe why its value is invalid
+fn apply_field(
+    metadata: &mut TaskMetadata,
+    fields: &mut TaskFields,
+    key: String,
+    value: &Value,
+) -> Result<(), String> {
+    match key.as_str() {
+        "id" => fields.id = single_value(&key, value)?,
+        "priority" => {
+            fields.priority = single_value(&key, value)?
+                .map(|p| parse_priority(&p))
+                .transpose()?
+        }
+        "dependencies" | "depends_on" => metadata.dependencies = list(&key, value)?,
+        "blocks" => metadata.blocks = list(&key, value)?,
+        "effort" | "estimated_effort" => metadata.effort = single_value(&key, value)?,
+        "status" => {
+            fields.status = single_value(&key, value)?
+                .map(|s| parse_status(&s))
+                .transpose()?
+        }
+        "blocked_reason" => fields.blocked_reason = single_value(&key, value)?,
+        "tags" => metadata.tags = list(&key, value)?,
+        "issue" | "issues" => fields.issues.extend(list(&key, value)?),
+        "due" => {
+            fields.due_date = single_value(&key, value)?
+                .map(|d| parse_date(&d))
+                .transpose()?
+        }
+        _ => {
+            metadata.custom.insert(key, render(value));
+        }
+    }
+    Ok(())
+}
+
+/// Convert a TOML value into the equivalent YAML value
+pub(crate) fn toml_to_yaml(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(text) => Value::String(text),
+        toml::Value::Integer(number) => Value::Number(number.into()),
+        toml::Value::Float(number) => Value::Number(number.into()),
+        toml::Value::Boolean(flag) => Value::Bool(flag),
+        toml::Value::Datetime(datetime) => Value::String(datetime.to_string()),
+        toml::Value::Array(items) => Value::Sequence(items.into_iter().map(toml_to_yaml).collect()),
+        toml::Value::Table(table) => Value::Mapping(
+            table
+                .into_iter()
+                .map(|(key, value)| (Value::String(key), toml_to_yaml(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// A key that holds one value
+fn single_value(key: &str, value: &Value) -> Result<Option<String>, String> {
+    match value {
+        Value::Sequence(_) | Value::Mapping(_) => Err(format!("`{}` must be a single value", key)),
+        _ => Ok(scalar(value)),
+    }
+}
+
+/// A key that holds a list, also accepting a single value
+fn list(key: &str, value: &Value) -> Result<Vec<String>, String> {
+    match value {
+        Value::Sequence(items) => items
+            .iter()
+            .map(|item| single_value(key, item))
+            .filter_map(Result::transpose)
+            .collect(),
+        _ => Ok(single_value(key, value)?.into_iter().collect()),
+    }
+}
+
+/// Text of a scalar value (`None` for null or empty strings)
+fn scalar(value: &Value) -> Option<String> {
+    let text = match value {
+        Value::String(text) => text.trim().to_string(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(flag) => flag.to_string(),
+        Value::Tagged(tagged) => return scalar(&tagged.value),
+        Value::Null | Value::Sequence(_) | Value::Mapping(_) => return None,
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+/// Render any value as text for custom fields
+fn render(value: &Value) -> String {
+    match value {
+        Value::Sequence(items) => items.iter().map(render).collect::<Vec<_>>().join(", "),
+        Value::Mapping(_) => serde_norway::to_string(value)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+        _ => scalar(value).unwrap_or_default(),
+    }
+}
+
+fn parse_priority(text: &str) -> Result<TaskPriority, String> {
+    TaskPriority::from_name(text)
+        .ok_or_else(|| format!("invalid priority `{}` (expected high, medium or low)", text))
+}
+
+fn parse_status(text: &str) -> Result<TaskStatus, String> {
+    TaskStatus::from_name(text).ok_or_else(|| {
+        format!(
+            "invalid status `{}` (expected open, in-progress, blocked or done)",
+            text
+        )
+    })
+}
+
+fn parse_date(text: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .map_err(|_| format!("invalid due date `{}` (expected YYYY-MM-DD)", text))
+}
+
+fn invalid(message: &str) -> TodoError {
+    TodoError::Parse(message.to_string())
+}
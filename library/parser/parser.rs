@@ -3,11 +3,75 @@
 //! This microcrate provides parsers for extracting TODO comments from source files
 //! and task definitions from markdown files.
 
+mod parser_attributes;
+mod parser_checklist;
+mod parser_directives;
+mod parser_grammar;
+mod parser_ids;
+mod parser_lexer;
+mod parser_owners;
+mod parser_status;
+mod parser_tags;
+
 use std::fs;
 use std::path::Path;
-use regex::Regex;
 
-use autodomd_library_common::{Task, TaskCategory, TaskPriority, TodoResult};
+use autodomd_library_common::{
+    Task, TaskCategory, TaskLocation, TaskPriority, TaskSource, TaskStatus, TodoResult,
+};
+use autodomd_library_language::{LanguageRegistry, LanguageSyntax};
+use autodomd_library_metadata::{parse_frontmatter, Frontmatter};
+
+use parser_attributes::{parse_issue_reference, parse_tag_attributes};
+use parser_checklist::extract_subtasks;
+use parser_directives::{has_directive, has_ignore_file_directive, Suppressions};
+use parser_grammar::near_miss;
+use parser_ids::{code_task_id, markdown_task_id};
+use parser_lexer::{lex_comments, CommentKind, CommentLine};
+pub use parser_owners::OwnerAliases;
+use parser_status::split_status_marker;
+use parser_tags::TagMatcher;
+pub use parser_tags::{TagKeyword, TagRegistry};
+
+/// Configuration for source file parsing
+#[derive(Debug, Clone, Default)]
+pub struct ParserConfig {
+    /// Tag keywords recognized in source comments
+    pub tags: TagRegistry,
+    /// Aliases used to collapse assignee handles into one owner
+    pub owner_aliases: OwnerAliases,
+    /// Comment syntax of each supported language
+    pub languages: LanguageRegistry,
+    /// Only accept TODOs that start the comment and follow the grammar exactly,
+    /// warning about near-misses instead of guessing
+    pub strict: bool,
+}
+
+/// Parser state shared by every file in one parse run
+struct ParseContext<'a> {
+    config: &'a ParserConfig,
+    tags: TagMatcher<'a>,
+    /// Syntax for files no registered language claims
+    fallback_syntax: LanguageSyntax,
+}
+
+impl<'a> ParseContext<'a> {
+    fn new(config: &'a ParserConfig) -> Self {
+        Self {
+            config,
+            tags: config.tags.matcher(),
+            fallback_syntax: LanguageSyntax::fallback(),
+        }
+    }
+
+    /// Get the comment syntax for a file
+    fn syntax_for(&self, file_path: &Path, content: &str) -> &LanguageSyntax {
+        self.config
+            .languages
+            .detect(file_path, content.lines().next())
+            .unwrap_or(&self.fallback_syntax)
+    }
+}
 
 /// Parse markdown files for TODO tasks
 pub fn parse_markdown_files(files: &[std::path::PathBuf]) -> TodoResult<Vec<Task>> {
@@ -17,7 +81,11 @@ pub fn parse_markdown_files(files: &[std::path::PathBuf]) -> TodoResult<Vec<Task
         match parse_markdown_file(file_path) {
             Ok(file_tasks) => tasks.extend(file_tasks),
             Err(e) => {
-                eprintln!("Warning: Failed to parse markdown file {}: {}", file_path.display(), e);
+                eprintln!(
+                    "Warning: Failed to parse markdown file {}: {}",
+                    file_path.display(),
+                    e
+                );
                 // Continue with other files
             }
         }
@@ -29,91 +97,86 @@ pub fn parse_markdown_files(files: &[std::path::PathBuf]) -> TodoResult<Vec<Task
 /// Parse a single markdown file for TODO tasks
 fn parse_markdown_file(file_path: &Path) -> TodoResult<Vec<Task>> {
     let content = fs::read_to_string(file_path)?;
+    parse_markdown_content(&content, file_path)
+}
+
+/// Parse the content of a markdown task file
+fn parse_markdown_content(content: &str, file_path: &Path) -> TodoResult<Vec<Task>> {
+    if has_ignore_file_directive(content) {
+        return Ok(Vec::new());
+    }
+
+    let Frontmatter {
+        metadata,
+        fields,
+        body,
+        mut problems,
+    } = parse_frontmatter(content)?;
+
+    let mut issues = Vec::new();
+    for issue in fields.issues {
+        match frontmatter_issue_reference(&issue) {
+            Some(reference) => issues.push(reference),
+            None => problems.push(format!(
+                "invalid issue reference `{}` (expected 123, #123 or PROJ-123)",
+                issue
+            )),
+        }
+    }
+    for problem in problems {
+        eprintln!("Warning: {}: {}", file_path.display(), problem);
+    }
 
     // Extract category from directory structure
     let category = extract_category_from_path(file_path);
 
-    // Extract priority from metadata (default to Medium)
-    let priority = extract_priority_from_content(&content);
+    // Priority from metadata, then the old **Priority:** line (default to Medium)
+    let priority = fields
+        .priority
+        .or_else(|| extract_priority_line(&body))
+        .unwrap_or(TaskPriority::Medium);
 
-    // Look for the first H1 header
-    if let Some(title) = extract_first_h1(&content) {
-        let task = Task::from_markdown_with_priority(
-            title.to_string(),
-            category,
-            priority,
-            file_path.to_path_buf(),
-        );
-        Ok(vec![task])
-    } else {
-        // Fallback: use filename as title
-        let filename = file_path
+    // Use the first H1 header as the title, falling back to the filename
+    let title = extract_first_h1(&body).unwrap_or_else(|| {
+        file_path
             .file_stem()
             .and_then(|s| s.to_str())
-            .unwrap_or("Unknown Task");
-
-        let task = Task::from_markdown_with_priority(
-            filename.to_string(),
-            category,
-            priority,
-            file_path.to_path_buf(),
-        );
-        Ok(vec![task])
-    }
+            .unwrap_or("Unknown Task")
+    });
+
+    let mut task = Task::from_markdown_with_priority(
+        title.to_string(),
+        category,
+        priority,
+        file_path.to_path_buf(),
+    );
+    task.id = markdown_task_id(file_path, fields.id.as_deref());
+    task.issues = issues;
+    task.due_date = fields.due_date;
+    task.status = fields.status.unwrap_or_default();
+    task.blocked_reason = fields.blocked_reason;
+    task.metadata = metadata;
+    task.subtasks = extract_subtasks(content);
+    Ok(vec![task])
 }
 
-/// Extract priority from markdown content (looks for YAML metadata or **Priority:** line)
-fn extract_priority_from_content(content: &str) -> TaskPriority {
-    // First try to extract from YAML metadata block
-    if let Some(yaml_block) = extract_yaml_metadata(content) {
-        if let Some(priority_line) = yaml_block.lines().find(|line| line.starts_with("priority:")) {
-            if let Some(priority_str) = priority_line.strip_prefix("priority:").map(|s| s.trim().trim_matches('"')) {
-                return match priority_str.to_lowercase().as_str() {
-                    "high" => TaskPriority::High,
-                    "low" => TaskPriority::Low,
-                    _ => TaskPriority::Medium,
-                };
-            }
-        }
-    }
-
-    // Fallback to old **Priority:** format
-    for line in content.lines() {
-        if let Some(priority_str) = line.strip_prefix("**Priority:**") {
-            let priority = priority_str.trim().to_lowercase();
-            return match priority.as_str() {
-                "high" => TaskPriority::High,
-                "low" => TaskPriority::Low,
-                _ => TaskPriority::Medium,
-            };
-        }
-    }
-    TaskPriority::Medium // Default
+/// Issue reference from the `issues:` key, where a number may omit its `#`
+fn frontmatter_issue_reference(issue: &str) -> Option<String> {
+    parse_issue_reference(issue).or_else(|| parse_issue_reference(&format!("#{}", issue)))
 }
 
-/// Extract YAML metadata block from markdown content
-fn extract_yaml_metadata(content: &str) -> Option<&str> {
-    let lines: Vec<&str> = content.lines().collect();
-
-    // Look for ```yaml or ``` followed by yaml content
-    let mut in_yaml_block = false;
-    let mut yaml_start = None;
-
-    for (i, line) in lines.iter().enumerate() {
-        if line.trim() == "```yaml" || (line.trim() == "```" && i < lines.len() - 1 && lines[i + 1].contains("priority:")) {
-            in_yaml_block = true;
-            yaml_start = Some(i + 1);
-        } else if in_yaml_block && line.trim() == "```" {
-            if let Some(start) = yaml_start {
-                return Some(&content[lines[start..i].iter().map(|s| s.len() + 1).sum::<usize>() - 1..lines[i].as_ptr() as usize - content.as_ptr() as usize]);
-            }
-        }
-    }
-
-    None
+/// Extract priority from the old `**Priority:** high` line format
+fn extract_priority_line(content: &str) -> Option<TaskPriority> {
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("**Priority:**"))
+        .map(|priority| TaskPriority::from_name(priority).unwrap_or(TaskPriority::Medium))
 }
 
 /// Extract category from the directory path relative to todo/
+///
+/// Every directory between `todo/` and the file becomes one level of the
+/// category, so `todo/backend/auth/login.md` is in `Backend/Auth`.
 fn extract_category_from_path(file_path: &Path) -> TaskCategory {
     // Convert path to string for easier manipulation
     let path_str = file_path.to_string_lossy();
@@ -122,49 +185,54 @@ fn extract_category_from_path(file_path: &Path) -> TaskCategory {
     if let Some(todo_pos) = path_str.find("todo/") {
         let after_todo = &path_str[todo_pos + 5..]; // Skip "todo/"
 
-        // Find the next directory separator
-        if let Some(separator_pos) = after_todo.find('/') {
-            let category_dir = &after_todo[..separator_pos];
-
-            // Capitalize first letter for better display
-            let capitalized = category_dir
-                .chars()
-                .enumerate()
-                .map(|(i, c)| if i == 0 { c.to_ascii_uppercase() } else { c })
-                .collect::<String>();
+        // Directories only; the last component is the file itself
+        let mut components: Vec<&str> = after_todo.split('/').collect();
+        components.pop();
 
-            TaskCategory::Custom(capitalized)
-        } else {
-            // No subdirectory, check if it's directly in todo/
-            TaskCategory::General
-        }
+        TaskCategory::from_segments(components.into_iter().map(capitalize))
     } else {
         // Not in todo/ directory, use general category
         TaskCategory::General
     }
 }
 
+/// Capitalize the first letter of a directory name for better display
+fn capitalize(name: &str) -> String {
+    name.chars()
+        .enumerate()
+        .map(|(i, c)| if i == 0 { c.to_ascii_uppercase() } else { c })
+        .collect()
+}
+
 /// Extract the first H1 header from markdown content
 fn extract_first_h1(content: &str) -> Option<&str> {
     // Look for lines starting with # followed by a space
     for line in content.lines() {
         let trimmed = line.trim();
-        if trimmed.starts_with("# ") {
-            return Some(&trimmed[2..]); // Skip "# "
+        if let Some(title) = trimmed.strip_prefix("# ") {
+            return Some(title);
         }
     }
     None
 }
 
 /// Parse source files for TODO comments
-pub fn parse_source_files(files: &[std::path::PathBuf]) -> TodoResult<Vec<Task>> {
+pub fn parse_source_files(
+    files: &[std::path::PathBuf],
+    config: &ParserConfig,
+) -> TodoResult<Vec<Task>> {
     let mut tasks = Vec::new();
+    let context = ParseContext::new(config);
 
     for file_path in files {
-        match parse_source_file(file_path) {
+        match parse_source_file(file_path, &context) {
             Ok(file_tasks) => tasks.extend(file_tasks),
             Err(e) => {
-                eprintln!("Warning: Failed to parse source file {}: {}", file_path.display(), e);
+                eprintln!(
+                    "Warning: Failed to parse source file {}: {}",
+                    file_path.display(),
+                    e
+                );
                 // Continue with other files
             }
         }
@@ -174,87 +242,744 @@ pub fn parse_source_files(files: &[std::path::PathBuf]) -> TodoResult<Vec<Task>>
 }
 
 /// Parse a single source file for TODO comments
-fn parse_source_file(file_path: &Path) -> TodoResult<Vec<Task>> {
+fn parse_source_file(file_path: &Path, context: &ParseContext) -> TodoResult<Vec<Task>> {
     let content = fs::read_to_string(file_path)?;
-    let extension = file_path.extension().and_then(|e| e.to_str()).unwrap_or("");
-
-    let comment_patterns = get_comment_patterns(extension);
-    let mut tasks = Vec::new();
 
-    for (line_number, line) in content.lines().enumerate() {
-        if let Some(task) = parse_todo_line(line, &comment_patterns, line_number + 1, file_path) {
-            tasks.push(task);
-        }
-    }
-
-    Ok(tasks)
+    let syntax = context.syntax_for(file_path, &content);
+    Ok(parse_source_content(&content, syntax, context, file_path))
 }
 
-/// Get comment patterns for a file extension
-fn get_comment_patterns(extension: &str) -> Vec<&'static str> {
-    match extension {
-        "rs" => vec!["//", "/*"], // Rust
-        "js" | "ts" | "jsx" | "tsx" => vec!["//", "/*"], // JavaScript/TypeScript
-        "py" => vec!["#"], // Python
-        "java" | "c" | "cpp" | "cc" | "cxx" | "h" | "hpp" => vec!["//", "/*"], // C/C++/Java
-        "go" => vec!["//"], // Go
-        "rb" => vec!["#"], // Ruby
-        "php" => vec!["//", "#", "/*"], // PHP
-        "swift" => vec!["//", "/*"], // Swift
-        "kt" => vec!["//", "/*"], // Kotlin
-        "scala" => vec!["//", "/*"], // Scala
-        _ => vec!["//", "#", "/*"], // Default patterns
+/// Parse source content for TODO comments, folding continuation lines into each task
+fn parse_source_content(
+    content: &str,
+    syntax: &LanguageSyntax,
+    context: &ParseContext,
+    file_path: &Path,
+) -> Vec<Task> {
+    let mut tasks = Vec::new();
+
+    let comments = lex_comments(content, syntax);
+    let suppressions = Suppressions::from_comments(&comments);
+    if suppressions.file {
+        return tasks;
     }
-}
 
-/// Parse a single line for TODO comments
-fn parse_todo_line(line: &str, comment_patterns: &[&str], line_number: usize, file_path: &Path) -> Option<Task> {
-    let trimmed = line.trim();
-
-    // Only process lines that start with a comment pattern (after trimming whitespace)
-    // This avoids matching TODO patterns inside string literals
-    for &pattern in comment_patterns {
-        if trimmed.starts_with(pattern) {
-            let after_comment = &trimmed[pattern.len()..];
-
-            // Look for TODO followed by optional category
-            if let Some(todo_match) = extract_todo_from_comment(after_comment.trim_start()) {
-                return Some(Task::from_code(
-                    todo_match.title,
-                    todo_match.category,
-                    file_path.to_path_buf(),
+    for comment in comments {
+        let mut pending: Option<PendingTodo> = None;
+
+        for CommentLine {
+            number: line_number,
+            column,
+            text,
+        } in comment.lines
+        {
+            let todo_match = extract_todo_from_comment(&text, context);
+            if context.config.strict && !suppressions.suppresses(line_number) {
+                report_grammar_problems(
+                    &text,
+                    todo_match.as_ref(),
+                    context,
+                    file_path,
                     line_number,
-                ));
+                );
+            }
+
+            if let Some(todo_match) = todo_match {
+                tasks.extend(pending.take().map(|p| p.into_task(file_path)));
+                pending = Some(PendingTodo {
+                    column: column + todo_match.tag_offset,
+                    todo_match,
+                    start_line: line_number,
+                    end_line: line_number,
+                });
+            } else if let Some(current) = pending
+                .as_mut()
+                .filter(|current| current.continues_with(comment.kind, column, &text))
+            {
+                current.todo_match.title.push(' ');
+                current.todo_match.title.push_str(&text);
+                current.end_line = line_number;
+            } else {
+                tasks.extend(pending.take().map(|p| p.into_task(file_path)));
             }
         }
+
+        tasks.extend(pending.map(|p| p.into_task(file_path)));
     }
 
-    None
+    // Drop tasks hidden by `autodomd:ignore` directives
+    tasks.retain(|task| {
+        !task
+            .location
+            .line_number
+            .is_some_and(|line| suppressions.suppresses(line))
+    });
+    tasks
+}
+
+/// Whether `text` starts a new sentence after `previous`: a capital letter
+/// following a sentence-ending period, `!` or `?`
+fn starts_sentence(previous: &str, text: &str) -> bool {
+    previous.ends_with(['.', '!', '?']) && text.starts_with(char::is_uppercase)
 }
 
 /// Extract TODO information from a comment
 struct TodoMatch {
     title: String,
     category: TaskCategory,
+    tag: String,
+    priority: TaskPriority,
+    assignee: Option<String>,
+    issues: Vec<String>,
+    due_date: Option<chrono::NaiveDate>,
+    status: TaskStatus,
+    blocked_reason: Option<String>,
+    /// Attributes that matched none of the known forms
+    unrecognized: Vec<String>,
+    /// Characters in the comment text before the tag
+    tag_offset: usize,
 }
 
-fn extract_todo_from_comment(comment: &str) -> Option<TodoMatch> {
-    let trimmed = comment.trim();
+/// A TODO being assembled from one or more comment lines
+struct PendingTodo {
+    todo_match: TodoMatch,
+    start_line: usize,
+    end_line: usize,
+    /// Column of the tag on the first line (1-indexed)
+    column: usize,
+}
 
-    // Match: TODO(Category): Description
-    let todo_regex = Regex::new(r"TODO(?:\((\w+)\))?\s*:\s*(.+)").ok()?;
+impl PendingTodo {
+    /// Whether a comment line without a tag continues this TODO
+    ///
+    /// Blank lines and directives always end it. Block comment lines continue
+    /// it, line comments continue it when their text starts at or past the tag,
+    /// and doc comments continue it until a new sentence starts.
+    fn continues_with(&self, kind: CommentKind, column: usize, text: &str) -> bool {
+        if text.is_empty() || has_directive(text) {
+            return false;
+        }
 
-    if let Some(captures) = todo_regex.captures(trimmed) {
-        let category_str = captures.get(1).map(|m| m.as_str()).unwrap_or("");
-        let title = captures.get(2).map(|m| m.as_str().trim())?;
+        match kind {
+            CommentKind::Block => true,
+            CommentKind::Line => column >= self.column,
+            CommentKind::Doc => !starts_sentence(&self.todo_match.title, text),
+        }
+    }
+
+    fn into_task(self, file_path: &Path) -> Task {
+        let mut task = Task::new(
+            self.todo_match.title,
+            self.todo_match.category,
+            self.todo_match.priority,
+            TaskLocation::from_source_range(
+                file_path.to_path_buf(),
+                self.start_line,
+                self.end_line,
+            )
+            .with_column(self.column),
+            TaskSource::Code,
+        );
+        task.id = code_task_id(&self.todo_match.tag, &task.title, file_path);
+        task.tag = Some(self.todo_match.tag);
+        task.assignee = self.todo_match.assignee;
+        task.issues = self.todo_match.issues;
+        task.due_date = self.todo_match.due_date;
+        task.status = self.todo_match.status;
+        task.blocked_reason = self.todo_match.blocked_reason;
+        task
+    }
+}
 
-        let category = TaskCategory::from_str(category_str);
+fn extract_todo_from_comment(comment: &str, context: &ParseContext) -> Option<TodoMatch> {
+    let trimmed = comment.trim();
 
-        Some(TodoMatch {
-            title: title.to_string(),
-            category,
-        })
+    // Match: TAG(Category, @assignee): Description
+    let regex = if context.config.strict {
+        &context.tags.strict
     } else {
-        None
+        &context.tags.lenient
+    };
+    let captures = regex.as_ref()?.captures(trimmed)?;
+
+    let tag_match = captures.get(1)?;
+    let tag = tag_match.as_str();
+    let leading = comment.len() - comment.trim_start().len();
+    let tag_offset = comment[..leading + tag_match.start()].chars().count();
+    let attributes = parse_tag_attributes(captures.get(2).map(|m| m.as_str()).unwrap_or(""));
+    let mut title = captures.get(3).map(|m| m.as_str().trim())?;
+
+    // A leading `[wip]` / `[blocked: reason]` marker sets the status
+    let (status, blocked_reason) = match split_status_marker(title) {
+        Some(marker) if !marker.rest.is_empty() => {
+            title = marker.rest;
+            (marker.status, marker.blocked_reason)
+        }
+        _ => (TaskStatus::Open, None),
+    };
+
+    let priority = context
+        .tags
+        .registry
+        .get(tag)
+        .map(|keyword| keyword.priority)
+        .unwrap_or(TaskPriority::Medium);
+
+    Some(TodoMatch {
+        title: title.to_string(),
+        category: TaskCategory::from_str(attributes.category.as_deref().unwrap_or("")),
+        tag: tag.to_string(),
+        priority,
+        assignee: attributes
+            .assignee
+            .map(|handle| context.config.owner_aliases.resolve(&handle)),
+        issues: attributes.issues,
+        due_date: attributes.due_date,
+        status,
+        blocked_reason,
+        unrecognized: attributes.unrecognized,
+        tag_offset,
+    })
+}
+
+/// Warn about a comment line that does not follow the strict TODO grammar
+fn report_grammar_problems(
+    text: &str,
+    todo_match: Option<&TodoMatch>,
+    context: &ParseContext,
+    file_path: &Path,
+    line_number: usize,
+) {
+    let problems = match todo_match {
+        Some(todo_match) => todo_match
+            .unrecognized
+            .iter()
+            .map(|attribute| format!("unrecognized attribute `{}`", attribute))
+            .collect(),
+        None => near_miss(text, &context.tags)
+            .into_iter()
+            .collect::<Vec<_>>(),
+    };
+
+    for problem in problems {
+        eprintln!(
+            "Warning: {}:{}: {}",
+            file_path.display(),
+            line_number,
+            problem
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_rust(content: &str) -> Vec<Task> {
+        parse_rust_with(content, &ParserConfig::default())
+    }
+
+    fn parse_rust_with(content: &str, config: &ParserConfig) -> Vec<Task> {
+        let context = ParseContext::new(config);
+        let path = Path::new("test.rs");
+        parse_source_content(content, context.syntax_for(path, content), &context, path)
+    }
+
+    #[test]
+    fn test_single_line_todo() {
+        let tasks = parse_rust("fn main() {}\n// TODO(Auth): Validate tokens\n");
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "Validate tokens");
+        assert_eq!(tasks[0].category, TaskCategory::from_str("Auth"));
+        assert_eq!(tasks[0].location.line_number, Some(2));
+        assert_eq!(tasks[0].location.end_line_number, Some(2));
+    }
+
+    #[test]
+    fn test_line_comment_continuation() {
+        let content = "// TODO: Cache the parsed result\n//   so repeated lookups are cheap\n//\n// Unrelated note\nfn f() {}\n";
+        let tasks = parse_rust(content);
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(
+            tasks[0].title,
+            "Cache the parsed result so repeated lookups are cheap"
+        );
+        assert_eq!(tasks[0].location.line_number, Some(1));
+        assert_eq!(tasks[0].location.end_line_number, Some(2));
+    }
+
+    #[test]
+    fn test_tag_column() {
+        let content = "fn main() {\n    //  TODO: Indented\n    let é = 1; // FIXME: Trailing\n}\n/*\n * HACK: In a block\n */\n";
+        let tasks = parse_rust(content);
+
+        let columns: Vec<_> = tasks
+            .iter()
+            .map(|task| task.location.column_number)
+            .collect();
+        assert_eq!(columns, vec![Some(9), Some(19), Some(4)]);
+    }
+
+    #[test]
+    fn test_line_comment_at_tag_column_is_folded() {
+        let tasks = parse_rust("// FIXME: wrapped todo that\n// continues here\nfn f() {}\n");
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "wrapped todo that continues here");
+        assert_eq!(tasks[0].location.end_line_number, Some(2));
+    }
+
+    #[test]
+    fn test_unindented_lines_are_not_folded() {
+        let content = "    // TODO: Handle empty input\n// Unrelated note\nfn f() {}\n";
+        let tasks = parse_rust(content);
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "Handle empty input");
+        assert_eq!(tasks[0].location.end_line_number, Some(1));
+    }
+
+    #[test]
+    fn test_doc_comment_prose_ends_todo() {
+        let content = concat!(
+            "/// TODO: Handle empty\n",
+            "/// input without panicking.\n",
+            "/// Returns the parsed value\n",
+            "/// or an error.\n",
+            "fn f() {}\n",
+        );
+        let tasks = parse_rust(content);
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "Handle empty input without panicking.");
+        assert_eq!(tasks[0].location.end_line_number, Some(2));
+    }
+
+    #[test]
+    fn test_multi_line_block_comment() {
+        let content = "/* TODO(Perf): Avoid cloning\n * the whole buffer here\n */\nfn f() {}\n";
+        let tasks = parse_rust(content);
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "Avoid cloning the whole buffer here");
+        assert_eq!(tasks[0].location.line_number, Some(1));
+        assert_eq!(tasks[0].location.end_line_number, Some(2));
+    }
+
+    #[test]
+    fn test_single_line_block_comment_strips_terminator() {
+        let tasks = parse_rust("/* TODO: Remove this shim */\n");
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "Remove this shim");
+    }
+
+    #[test]
+    fn test_todo_inside_block_after_first_line() {
+        let content = "/*\n * Helper utilities.\n *\n * TODO: Split into modules\n */\n";
+        let tasks = parse_rust(content);
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "Split into modules");
+        assert_eq!(tasks[0].location.line_number, Some(4));
+    }
+
+    #[test]
+    fn test_consecutive_todos_are_separate_tasks() {
+        let tasks = parse_rust("// TODO: First\n// TODO: Second\n");
+
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].title, "First");
+        assert_eq!(tasks[1].title, "Second");
+    }
+
+    #[test]
+    fn test_builtin_tags_map_to_priorities() {
+        let content = "// FIXME: Crash on empty input\n\n// OPTIMIZE: Avoid the double pass\n\n// TODO: Tidy up\n";
+        let tasks = parse_rust(content);
+
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[0].tag.as_deref(), Some("FIXME"));
+        assert_eq!(tasks[0].priority, TaskPriority::High);
+        assert_eq!(tasks[1].tag.as_deref(), Some("OPTIMIZE"));
+        assert_eq!(tasks[1].priority, TaskPriority::Low);
+        assert_eq!(tasks[2].tag.as_deref(), Some("TODO"));
+        assert_eq!(tasks[2].priority, TaskPriority::Medium);
+    }
+
+    #[test]
+    fn test_custom_tag_registration() {
+        let mut config = ParserConfig::default();
+        config.tags.register("SECURITY", TaskPriority::High);
+
+        let tasks = parse_rust_with("// SECURITY(Auth): Rotate signing keys\n", &config);
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].tag.as_deref(), Some("SECURITY"));
+        assert_eq!(tasks[0].priority, TaskPriority::High);
+        assert_eq!(tasks[0].category, TaskCategory::from_str("Auth"));
+    }
+
+    #[test]
+    fn test_tag_requires_word_boundary() {
+        assert!(parse_rust("// DEBUG: dump state\n").is_empty());
+    }
+
+    #[test]
+    fn test_assignee_syntax() {
+        let content = "// TODO(@alice): Write docs\n\n// FIXME(Auth, @bob): Check expiry\n";
+        let tasks = parse_rust(content);
+
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].assignee.as_deref(), Some("alice"));
+        assert_eq!(tasks[0].category, TaskCategory::General);
+        assert_eq!(tasks[1].assignee.as_deref(), Some("bob"));
+        assert_eq!(tasks[1].category, TaskCategory::from_str("Auth"));
+    }
+
+    #[test]
+    fn test_owner_aliases_collapse_handles() {
+        let config = ParserConfig {
+            owner_aliases: OwnerAliases::parse(
+                "# team\nAlice Smith <alice> <asmith>\n<bob> <robert>\n",
+            )
+            .unwrap(),
+            ..Default::default()
+        };
+
+        let content = "// TODO(@asmith): One\n\n// TODO(@ALICE): Two\n\n// TODO(@robert): Three\n\n// TODO(@carol): Four\n";
+        let owners: Vec<String> = parse_rust_with(content, &config)
+            .into_iter()
+            .filter_map(|task| task.assignee)
+            .collect();
+
+        assert_eq!(owners, vec!["Alice Smith", "Alice Smith", "bob", "carol"]);
+    }
+
+    #[test]
+    fn test_owner_aliases_reject_malformed_line() {
+        assert!(OwnerAliases::parse("Alice Smith\n").is_err());
+    }
+
+    #[test]
+    fn test_issue_references_in_code() {
+        let content =
+            "// TODO(#123): Handle retries\n\n// FIXME(Auth, PROJ-45, @alice): Expire sessions\n";
+        let tasks = parse_rust(content);
+
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].issues, vec!["123"]);
+        assert_eq!(tasks[0].category, TaskCategory::General);
+        assert_eq!(tasks[1].issues, vec!["PROJ-45"]);
+        assert_eq!(tasks[1].category, TaskCategory::from_str("Auth"));
+        assert_eq!(tasks[1].assignee.as_deref(), Some("alice"));
+    }
+
+    #[test]
+    fn test_issue_references_in_markdown_metadata() {
+        let content =
+            "# Task\n\n```yaml\npriority: high\nissue: 123\nissues: [\"PROJ-7\", \"#9\", \"#\", soon]\n```\n";
+        let tasks = parse_markdown_content(content, Path::new("todo/task.md")).unwrap();
+
+        assert_eq!(tasks[0].issues, vec!["123", "PROJ-7", "9"]);
+        assert_eq!(tasks[0].priority, TaskPriority::High);
+    }
+
+    #[test]
+    fn test_due_dates() {
+        let tasks = parse_rust("// TODO(Auth, due:2026-12-01): Remove legacy login\n\n// HACK(due:2026-13-40): Bad date\n");
+
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(
+            tasks[0].due_date,
+            chrono::NaiveDate::from_ymd_opt(2026, 12, 1)
+        );
+        assert_eq!(tasks[0].category, TaskCategory::from_str("Auth"));
+        assert_eq!(tasks[1].due_date, None);
+
+        let content = "# Task\n\n```yaml\npriority: low\ndue: \"2025-06-30\"\n```\n";
+        let tasks = parse_markdown_content(content, Path::new("todo/task.md")).unwrap();
+        assert_eq!(
+            tasks[0].due_date,
+            chrono::NaiveDate::from_ymd_opt(2025, 6, 30)
+        );
+    }
+
+    #[test]
+    fn test_task_status() {
+        let tasks = parse_rust("// TODO(Auth): [wip] Rotate keys\n\n// FIXME: [blocked: waiting on #42] Drop endpoint\n\n// TODO: Plain\n");
+
+        assert_eq!(tasks[0].status, TaskStatus::InProgress);
+        assert_eq!(tasks[0].title, "Rotate keys");
+        assert_eq!(tasks[1].status, TaskStatus::Blocked);
+        assert_eq!(tasks[1].blocked_reason.as_deref(), Some("waiting on #42"));
+        assert_eq!(tasks[2].status, TaskStatus::Open);
+
+        let content = "---\nstatus: blocked\nblocked_reason: needs design review\n---\n# Task\n";
+        let tasks = parse_markdown_content(content, Path::new("todo/task.md")).unwrap();
+        assert_eq!(tasks[0].status, TaskStatus::Blocked);
+        assert_eq!(
+            tasks[0].blocked_reason.as_deref(),
+            Some("needs design review")
+        );
+    }
+
+    #[test]
+    fn test_trailing_comment_todo() {
+        let content = "let x = compute(); // TODO: cache this\nlet y = 2; // unrelated\n";
+        let tasks = parse_rust(content);
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "cache this");
+        assert_eq!(tasks[0].location.line_number, Some(1));
+    }
+
+    #[test]
+    fn test_todo_inside_literals_is_ignored() {
+        let content = concat!(
+            "let a = \"// TODO: not a task\";\n",
+            "let b = r#\"/* TODO: raw */ \"quoted\" // TODO: raw\"#;\n",
+            "let c = '\"'; // TODO: after char literal\n",
+            "fn f<'a>(s: &'a str) -> &'a str { s } // FIXME: after lifetimes\n",
+            "let d = ['\\'','\"']; // TODO: after escaped quote\n",
+            "let e = \"escaped \\\" // TODO: still string\";\n",
+        );
+        let titles: Vec<String> = parse_rust(content)
+            .into_iter()
+            .map(|task| task.title)
+            .collect();
+
+        assert_eq!(
+            titles,
+            vec![
+                "after char literal",
+                "after lifetimes",
+                "after escaped quote"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unclosed_strings_are_searched_once() {
+        // Every quote is escaped, so each one opens a string that never closes
+        let content = format!("{}\n// TODO: after stray quotes\n", "\\\"".repeat(100_000));
+        let tasks = parse_rust(&content);
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "after stray quotes");
+        assert_eq!(tasks[0].location.line_number, Some(2));
+    }
+
+    #[test]
+    fn test_nested_block_comments() {
+        let content = "/* outer\n   /* inner */\n   // TODO: commented-out code\n*/\nfn f() {} // TODO: real\n";
+        let tasks = parse_rust(content);
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "real");
+        assert_eq!(tasks[0].location.line_number, Some(5));
+
+        // C block comments end at the first `*/`
+        let path = Path::new("main.c");
+        let config = ParserConfig::default();
+        let context = ParseContext::new(&config);
+        let tasks =
+            parse_source_content(content, context.syntax_for(path, content), &context, path);
+
+        let titles: Vec<&str> = tasks.iter().map(|task| task.title.as_str()).collect();
+        assert_eq!(titles, vec!["commented-out code", "real"]);
+    }
+
+    #[test]
+    fn test_multi_line_string_skipped() {
+        let path = Path::new("script.py");
+        let config = ParserConfig::default();
+        let context = ParseContext::new(&config);
+        let content =
+            "doc = \"\"\"\n# TODO: inside docstring\n\"\"\"\nvalue = 1  # TODO: real task\n";
+        let tasks =
+            parse_source_content(content, context.syntax_for(path, content), &context, path);
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "real task");
+        assert_eq!(tasks[0].location.line_number, Some(4));
+    }
+
+    #[test]
+    fn test_cpp_raw_string_skipped() {
+        let path = Path::new("main.cpp");
+        let config = ParserConfig::default();
+        let context = ParseContext::new(&config);
+        let content = "auto s = R\"x(// TODO: raw\n)x\"; // TODO: real\n";
+        let tasks =
+            parse_source_content(content, context.syntax_for(path, content), &context, path);
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "real");
+        assert_eq!(tasks[0].location.line_number, Some(2));
+    }
+
+    #[test]
+    fn test_shell_script_detected_by_shebang() {
+        let path = Path::new("bin/deploy");
+        let config = ParserConfig::default();
+        let context = ParseContext::new(&config);
+        let content =
+            "#!/usr/bin/env bash\necho \"$# args\" # TODO: validate args\n# FIXME: handle spaces\n";
+        let tasks =
+            parse_source_content(content, context.syntax_for(path, content), &context, path);
+
+        let titles: Vec<&str> = tasks.iter().map(|task| task.title.as_str()).collect();
+        assert_eq!(titles, vec!["validate args", "handle spaces"]);
+    }
+
+    #[test]
+    fn test_yaml_apostrophe_does_not_hide_comments() {
+        let path = Path::new(".github/workflows/ci.yml");
+        let config = ParserConfig::default();
+        let context = ParseContext::new(&config);
+        let content =
+            "name: Don't panic\nurl: http://example.com/#anchor\n# TODO: pin action versions\n";
+        let tasks =
+            parse_source_content(content, context.syntax_for(path, content), &context, path);
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "pin action versions");
+        assert_eq!(tasks[0].location.line_number, Some(3));
+    }
+
+    #[test]
+    fn test_categories_with_spaces_and_dashes() {
+        let tasks =
+            parse_rust("// TODO(UI-UX): Align icons\n// TODO(Data Model, @alice): Add indexes\n");
+
+        assert_eq!(tasks[0].category, TaskCategory::from_str("UI-UX"));
+        assert_eq!(tasks[1].category, TaskCategory::from_str("Data Model"));
+        assert_eq!(tasks[1].assignee.as_deref(), Some("alice"));
+    }
+
+    #[test]
+    fn test_doc_comments() {
+        let content = concat!(
+            "/// TODO(Docs): Document the error cases\n",
+            "//! FIXME: Crate docs are stale\n",
+            "/** TODO: Javadoc style */\n",
+            "/*! NOTE: Inner block doc */\n",
+        );
+        let config = ParserConfig {
+            strict: true,
+            ..Default::default()
+        };
+        let titles: Vec<String> = parse_rust_with(content, &config)
+            .into_iter()
+            .map(|task| task.title)
+            .collect();
+
+        assert_eq!(
+            titles,
+            vec![
+                "Document the error cases",
+                "Crate docs are stale",
+                "Javadoc style",
+                "Inner block doc"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strict_mode_anchors_tag() {
+        let content = "// see the TODO: list below\n// TODO fix this\n// TODO : Spaced\n// TODO(Auth): Real task\n";
+        let config = ParserConfig {
+            strict: true,
+            ..Default::default()
+        };
+
+        let lenient: Vec<String> = parse_rust(content)
+            .into_iter()
+            .map(|task| task.title)
+            .collect();
+        let strict: Vec<String> = parse_rust_with(content, &config)
+            .into_iter()
+            .map(|task| task.title)
+            .collect();
+
+        assert_eq!(lenient, vec!["Spaced", "Real task"]);
+        assert_eq!(strict, vec!["Real task"]);
+    }
+
+    #[test]
+    fn test_hierarchical_categories() {
+        let tasks = parse_rust("// TODO(Backend/Auth, @alice): Rotate signing keys\n");
+        assert_eq!(
+            tasks[0].category,
+            TaskCategory::Custom(vec!["Backend".to_string(), "Auth".to_string()])
+        );
+
+        assert_eq!(
+            extract_category_from_path(Path::new("todo/backend/auth/login.md")),
+            TaskCategory::from_str("Backend/Auth")
+        );
+        assert_eq!(
+            extract_category_from_path(Path::new("./todo/frontend/ui.md")),
+            TaskCategory::from_str("Frontend")
+        );
+        assert_eq!(
+            extract_category_from_path(Path::new("todo/readme.md")),
+            TaskCategory::General
+        );
+    }
+
+    #[test]
+    fn test_ignore_directives() {
+        let content = concat!(
+            "// TODO: Real task\n",
+            "// autodomd:ignore-next-line\n",
+            "\n",
+            "let fixture = 1; // TODO: Fixture text autodomd:ignore\n",
+            "// autodomd:ignore-next-line\n",
+            "// FIXME: Copied from upstream\n",
+            "// TODO: Another real task\n",
+        );
+        let titles: Vec<String> = parse_rust(content)
+            .into_iter()
+            .map(|task| task.title)
+            .collect();
+
+        // The directive ends the first task rather than joining its title
+        assert_eq!(titles, vec!["Real task", "Another real task"]);
+        assert!(parse_rust("// autodomd:ignore-file\n// TODO: Vendored\n").is_empty());
+        assert!(!has_ignore_file_directive("// autodomd:ignored-file"));
+    }
+
+    #[test]
+    fn test_markdown_frontmatter() {
+        let content = "---\npriority: high\ndependencies: [core-scanning]\nowner: alice\n---\n# Ship the release\n";
+        let tasks = parse_markdown_content(content, Path::new("todo/release/ship.md")).unwrap();
+
+        assert_eq!(tasks[0].title, "Ship the release");
+        assert_eq!(tasks[0].priority, TaskPriority::High);
+        assert_eq!(tasks[0].category, TaskCategory::from_str("Release"));
+        assert_eq!(tasks[0].metadata.dependencies, vec!["core-scanning"]);
+        assert_eq!(
+            tasks[0].metadata.custom.get("owner").map(String::as_str),
+            Some("alice")
+        );
+
+        // An invalid value is reported but the task is kept
+        let invalid = parse_markdown_content(
+            "---\npriority: urgent\n---\n# Task\n",
+            Path::new("todo/t.md"),
+        )
+        .unwrap();
+        assert_eq!(invalid[0].title, "Task");
+        assert_eq!(invalid[0].priority, TaskPriority::Medium);
+
+        let malformed = parse_markdown_content(
+            "---\npriority: [high\n---\n# Task\n",
+            Path::new("todo/t.md"),
+        );
+        assert!(malformed.is_err());
     }
 }
@@ -0,0 +1,117 @@
+//! Parsing of the attribute list that may follow a tag keyword
+//!
+//! The parenthesised part of a tag such as `TODO(Auth, @alice)` is a comma-separated list
+//! of attributes. A plain name or `Area/Sub-area` path is the task category, an
+//! `@handle` names the assignee, `#123` or `PROJ-45` reference issues in a
+//! tracker and `due:YYYY-MM-DD` sets a due date.
+//! Unrecognized attributes are collected so strict mode can report them.
+
+use chrono::NaiveDate;
+
+/// Attributes extracted from `TAG(...)`
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct TagAttributes {
+    /// Category name, if one was given
+    pub(crate) category: Option<String>,
+    /// Assignee handle without the leading `@`
+    pub(crate) assignee: Option<String>,
+    /// Issue identifiers without the leading `#`
+    pub(crate) issues: Vec<String>,
+    /// Due date from `due:YYYY-MM-DD`
+    pub(crate) due_date: Option<NaiveDate>,
+    /// Attributes that matched none of the forms above
+    pub(crate) unrecognized: Vec<String>,
+}
+
+/// Parse the contents of the parentheses after a tag keyword
+pub(crate) fn parse_tag_attributes(attributes: &str) -> TagAttributes {
+    let mut parsed = TagAttributes::default();
+
+    for attribute in attributes
+        .split(',')
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+    {
+        if !apply_attribute(&mut parsed, attribute) {
+            parsed.unrecognized.push(attribute.to_string());
+        }
+    }
+
+    parsed
+}
+
+/// Apply one attribute, returning false if it is not recognized or repeats a
+/// single-valued attribute
+fn apply_attribute(parsed: &mut TagAttributes, attribute: &str) -> bool {
+    if let Some(handle) = attribute
+        .strip_prefix('@')
+        .filter(|handle| is_handle(handle))
+    {
+        set_once(&mut parsed.assignee, handle.to_string())
+    } else if let Some(date) = attribute.strip_prefix("due:").and_then(parse_due_date) {
+        set_once(&mut parsed.due_date, date)
+    } else if let Some(issue) = parse_issue_reference(attribute) {
+        parsed.issues.push(issue);
+        true
+    } else if is_category(attribute) {
+        set_once(&mut parsed.category, attribute.to_string())
+    } else {
+        false
+    }
+}
+
+/// Set an optional value unless it is already set
+fn set_once<T>(slot: &mut Option<T>, value: T) -> bool {
+    let empty = slot.is_none();
+    if empty {
+        *slot = Some(value);
+    }
+    empty
+}
+
+/// Parse a due date in `YYYY-MM-DD` form
+pub(crate) fn parse_due_date(text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text.trim().trim_matches(&['"', '\''] as &[_]), "%Y-%m-%d").ok()
+}
+
+/// Parse an issue reference: `#123` or a tracker key like `PROJ-45`
+pub(crate) fn parse_issue_reference(text: &str) -> Option<String> {
+    let text = text.trim();
+
+    if let Some(number) = text.strip_prefix('#') {
+        return (!number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+            .then(|| number.to_string());
+    }
+
+    let (project, number) = text.split_once('-')?;
+    let valid_project = project
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_uppercase())
+        && project
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+    let valid_number = !number.is_empty() && number.chars().all(|c| c.is_ascii_digit());
+
+    (valid_project && valid_number).then(|| text.to_string())
+}
+
+/// Whether the text is a category path: names separated by `/`, each made of
+/// words joined by spaces, `-` or `_`
+fn is_category(text: &str) -> bool {
+    text.split('/').map(str::trim).all(|name| {
+        name.starts_with(char::is_alphanumeric)
+            && name.ends_with(char::is_alphanumeric)
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | ' '))
+    })
+}
+
+/// Whether the text is a valid assignee handle (word characters, `.` and `-`)
+fn is_handle(text: &str) -> bool {
+    !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == '.')
+}
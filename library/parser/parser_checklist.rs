@@ -0,0 +1,113 @@
+//! Checklist subtasks in markdown task files
+//!
+//! GitHub-style task list items (`- [ ]` and `- [x]`, also with `*` or `+`
+//! bullets) become subtasks of the file's task. Items inside the frontmatter
+//! or fenced code blocks are not subtasks.
+
+use autodomd_library_common::Subtask;
+
+/// Collect the checklist items of a markdown task file, with their line numbers
+pub(crate) fn extract_subtasks(content: &str) -> Vec<Subtask> {
+    let mut subtasks = Vec::new();
+    let mut lines = content.lines().enumerate().peekable();
+
+    // Skip `---` / `+++` frontmatter at the top of the file
+    if let Some(&(_, first_line)) = lines.peek() {
+        let delimiter = first_line.trim_start_matches('\u{feff}').trim_end();
+        if delimiter == "---" || delimiter == "+++" {
+            lines.next();
+            for (_, line) in lines.by_ref() {
+                if line.trim_end() == delimiter {
+                    break;
+                }
+            }
+        }
+    }
+
+    let mut fence: Option<&str> = None;
+    for (index, line) in lines {
+        let trimmed = line.trim_start();
+
+        if let Some(open) = fence {
+            if trimmed.starts_with(open) {
+                fence = None;
+            }
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker))
+        {
+            fence = Some(marker);
+            continue;
+        }
+
+        if let Some((done, title)) = parse_checklist_item(trimmed) {
+            subtasks.push(Subtask {
+                title: title.to_string(),
+                done,
+                line_number: index + 1,
+            });
+        }
+    }
+
+    subtasks
+}
+
+/// Parse `- [ ] text` / `- [x] text`, returning whether it is checked and its text
+fn parse_checklist_item(line: &str) -> Option<(bool, &str)> {
+    let rest = line.strip_prefix(['-', '*', '+'])?;
+    let rest = rest.strip_prefix([' ', '\t'])?.trim_start();
+
+    let done = match rest.get(..3)? {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+
+    let title = rest[3..].trim();
+    if title.is_empty() || !rest[3..].starts_with([' ', '\t']) {
+        return None;
+    }
+    Some((done, title))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_subtasks() {
+        let content = concat!(
+            "---\n",
+            "priority: high\n",
+            "---\n",
+            "# Login\n",
+            "\n",
+            "- [x] Design the form\n",
+            "- [ ] Validate input\n",
+            "  * [X] Nested item\n",
+            "- [ ]\n",
+            "- [link](docs.md)\n",
+            "```markdown\n",
+            "- [ ] Example in a code block\n",
+            "```\n",
+            "+ [ ] Connect the API\n",
+        );
+        let subtasks = extract_subtasks(content);
+
+        let summary: Vec<(&str, bool, usize)> = subtasks
+            .iter()
+            .map(|subtask| (subtask.title.as_str(), subtask.done, subtask.line_number))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Design the form", true, 6),
+                ("Validate input", false, 7),
+                ("Nested item", true, 8),
+                ("Connect the API", false, 14),
+            ]
+        );
+    }
+}
@@ -0,0 +1,87 @@
+//! Inline suppression directives
+//!
+//! Comments may carry directives that hide false-positive TODOs:
+//!
+//! - `autodomd:ignore` suppresses tasks starting on the same line
+//! - `autodomd:ignore-next-line` suppresses tasks starting on the following line
+//! - `autodomd:ignore-file` suppresses every task in the file
+
+use std::collections::HashSet;
+
+use crate::parser_lexer::Comment;
+
+/// Prefix shared by all directives
+const DIRECTIVE_PREFIX: &str = "autodomd:";
+
+/// Directives recognized in comments
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Directive {
+    Ignore,
+    IgnoreNextLine,
+    IgnoreFile,
+}
+
+/// Lines and files suppressed by directives
+#[derive(Debug, Default)]
+pub(crate) struct Suppressions {
+    /// Whether the whole file is suppressed
+    pub(crate) file: bool,
+    /// Suppressed line numbers (1-indexed)
+    lines: HashSet<usize>,
+}
+
+impl Suppressions {
+    /// Collect the directives found in a file's comments
+    pub(crate) fn from_comments(comments: &[Comment]) -> Self {
+        let mut suppressions = Self::default();
+
+        for line in comments.iter().flat_map(|comment| &comment.lines) {
+            for directive in find_directives(&line.text) {
+                match directive {
+                    Directive::Ignore => {
+                        suppressions.lines.insert(line.number);
+                    }
+                    Directive::IgnoreNextLine => {
+                        suppressions.lines.insert(line.number + 1);
+                    }
+                    Directive::IgnoreFile => suppressions.file = true,
+                }
+            }
+        }
+
+        suppressions
+    }
+
+    /// Whether a task starting on this line is suppressed
+    pub(crate) fn suppresses(&self, line_number: usize) -> bool {
+        self.file || self.lines.contains(&line_number)
+    }
+}
+
+/// Whether a comment line carries any directive
+pub(crate) fn has_directive(text: &str) -> bool {
+    find_directives(text).next().is_some()
+}
+
+/// Whether text (e.g. a whole markdown task file) contains `autodomd:ignore-file`
+pub(crate) fn has_ignore_file_directive(text: &str) -> bool {
+    find_directives(text).any(|directive| directive == Directive::IgnoreFile)
+}
+
+/// Find every directive in a piece of text
+fn find_directives(text: &str) -> impl Iterator<Item = Directive> + '_ {
+    text.match_indices(DIRECTIVE_PREFIX)
+        .filter_map(move |(index, _)| {
+            let name: String = text[index + DIRECTIVE_PREFIX.len()..]
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '-')
+                .collect();
+
+            match name.as_str() {
+                "ignore" => Some(Directive::Ignore),
+                "ignore-next-line" => Some(Directive::IgnoreNextLine),
+                "ignore-file" => Some(Directive::IgnoreFile),
+                _ => None,
+            }
+        })
+}
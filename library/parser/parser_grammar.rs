@@ -0,0 +1,106 @@
+//! Grammar of TODO comments and near-miss detection for strict mode
+//!
+//! After comment markers (`//`, `///`, `//!`, `#`, `/** ... */`, ...) are
+//! stripped, a TODO comment has the form:
+//!
+//! ```text
+//! todo        = tag [ "(" attributes ")" ] ":" { " " } description
+//! tag         = one of the registered keywords, matched case-sensitively
+//! attributes  = attribute { "," attribute }
+//! attribute   = category | "@" handle | issue | "due:" date
+//! category    = name { "/" name }
+//! name        = word { ( " " | "-" | "_" ) word }
+//! handle      = { word-char | "." | "-" }+
+//! issue       = "#" digits | PROJECT "-" digits
+//! date        = YYYY "-" MM "-" DD
+//! description = non-blank text running to the end of the line
+//! ```
+//!
+//! In strict mode the tag must start the comment. Comments that almost match
+//! (wrong case, missing `:`, tag mid-sentence, unknown attributes) are reported
+//! as warnings instead of being turned into tasks on a guess.
+
+use crate::parser_tags::TagMatcher;
+
+/// Describe why a comment that does not follow the strict grammar looks like it
+/// was meant to be a TODO, or `None` if it is ordinary prose
+pub(crate) fn near_miss(comment: &str, tags: &TagMatcher) -> Option<String> {
+    let text = comment.trim();
+
+    let Some((keyword, written)) = tags.leading_keyword(text) else {
+        let captures = tags.anywhere.as_ref()?.captures(text)?;
+        let tag = captures.get(1)?.as_str();
+        return Some(format!("`{}:` is not at the start of the comment", tag));
+    };
+
+    let rest = &text[written.len()..];
+    if written != keyword.name {
+        // "Note that ..." is prose; "Note: ..." or "todo(...)" was meant as a tag
+        return rest.starts_with([':', '(']).then(|| {
+            format!(
+                "`{}` should be written `{}`; tags are case-sensitive",
+                written, keyword.name
+            )
+        });
+    }
+
+    let after_attributes = match rest.strip_prefix('(') {
+        Some(inner) => match inner.find(')') {
+            Some(close) => &inner[close + 1..],
+            None => return Some(format!("unclosed `(` after `{}`", keyword.name)),
+        },
+        None => rest,
+    };
+
+    match after_attributes.strip_prefix(':') {
+        Some(description) if description.trim().is_empty() => {
+            Some(format!("`{}:` has no description", keyword.name))
+        }
+        Some(_) => None,
+        None if after_attributes.trim_start().starts_with(':') => Some(format!(
+            "unexpected space before `:` after `{}`",
+            keyword.name
+        )),
+        None => Some(format!("missing `:` after `{}`", keyword.name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser_tags::TagRegistry;
+
+    fn check(comment: &str) -> Option<String> {
+        let registry = TagRegistry::default();
+        near_miss(comment, &registry.matcher())
+    }
+
+    #[test]
+    fn test_prose_is_not_a_near_miss() {
+        assert_eq!(check("Returns the number of items"), None);
+        assert_eq!(check("Note that this allocates"), None);
+        assert_eq!(check("the TODO list is long"), None);
+    }
+
+    #[test]
+    fn test_near_misses_are_described() {
+        assert_eq!(
+            check("see the TODO: list below").unwrap(),
+            "`TODO:` is not at the start of the comment"
+        );
+        assert_eq!(
+            check("todo: lowercase").unwrap(),
+            "`todo` should be written `TODO`; tags are case-sensitive"
+        );
+        assert_eq!(check("TODO fix this").unwrap(), "missing `:` after `TODO`");
+        assert_eq!(
+            check("FIXME : spaced").unwrap(),
+            "unexpected space before `:` after `FIXME`"
+        );
+        assert_eq!(
+            check("TODO(Auth: unclosed").unwrap(),
+            "unclosed `(` after `TODO`"
+        );
+        assert_eq!(check("TODO(Auth):").unwrap(), "`TODO:` has no description");
+    }
+}
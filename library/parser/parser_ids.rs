@@ -0,0 +1,86 @@
+//! Stable task identifiers
+//!
+//! Markdown tasks use the `id:` key from their metadata, or else the file slug
+//! (`todo/010-core-scanning.md` is `core-scanning`). Code TODOs have no name to
+//! go by, so their ID is the lowercased tag plus a hash of the file path and
+//! description, e.g. `fixme-3f9a2c1e`. Moving a TODO within its file keeps the
+//! ID; editing its text or moving it to another file changes it.
+
+use std::path::Path;
+
+use autodomd_library_common::task_slug;
+
+/// ID of a markdown task, given the value of its `id:` key if any
+pub(crate) fn markdown_task_id(file_path: &Path, explicit_id: Option<&str>) -> String {
+    if let Some(id) = explicit_id.map(str::trim).filter(|id| !id.is_empty()) {
+        return id.to_string();
+    }
+
+    let stem = file_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("task");
+    let slug = task_slug(stem);
+    if slug.is_empty() {
+        "task".to_string()
+    } else {
+        slug
+    }
+}
+
+/// ID of a code TODO
+pub(crate) fn code_task_id(tag: &str, title: &str, file_path: &Path) -> String {
+    let path = file_path.to_string_lossy().replace('\\', "/");
+    let path = path.strip_prefix("./").unwrap_or(&path);
+
+    let hash = fnv1a(&format!("{}\0{}", path, title.trim()));
+    format!("{}-{:08x}", tag.to_lowercase(), hash as u32)
+}
+
+/// 64-bit FNV-1a, stable across platforms and Rust versions unlike `DefaultHasher`
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_task_ids() {
+        assert_eq!(
+            markdown_task_id(Path::new("todo/010-core-scanning.md"), None),
+            "core-scanning"
+        );
+        assert_eq!(
+            markdown_task_id(Path::new("todo/Login Page.md"), None),
+            "login-page"
+        );
+
+        assert_eq!(
+            markdown_task_id(Path::new("todo/010-core-scanning.md"), Some("auth-login")),
+            "auth-login"
+        );
+    }
+
+    #[test]
+    fn test_code_task_ids_are_stable() {
+        let id = code_task_id("FIXME", "Expire sessions", Path::new("./src/auth.rs"));
+
+        assert!(id.starts_with("fixme-") && id.len() == "fixme-".len() + 8);
+        assert_eq!(
+            id,
+            code_task_id("FIXME", "Expire sessions ", Path::new("src/auth.rs"))
+        );
+        assert_ne!(
+            id,
+            code_task_id("FIXME", "Expire tokens", Path::new("src/auth.rs"))
+        );
+        assert_ne!(
+            id,
+            code_task_id("FIXME", "Expire sessions", Path::new("src/session.rs"))
+        );
+    }
+}
@@ -0,0 +1,467 @@
+//! Comment lexer for source files
+//!
+//! Walks a file once, skipping string, raw string and character literals, and
+//! collects every comment whether it starts the line or trails code. Comment
+//! lines that belong together are grouped so multi-line TODOs can be folded.
+
+use std::collections::HashMap;
+
+use autodomd_library_language::{LanguageSyntax, RawStringStyle};
+
+/// A run of comment lines that belong together: one block comment, or
+/// consecutive full-line comments using the same marker
+pub(crate) struct Comment {
+    pub(crate) kind: CommentKind,
+    pub(crate) lines: Vec<CommentLine>,
+}
+
+/// How a comment was written, which decides how its lines continue a TODO
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CommentKind {
+    /// Consecutive line comments such as `//` or `#`
+    Line,
+    /// A block comment such as `/* */`
+    Block,
+    /// Documentation prose: `///`, `//!`, `/** */` or `/*! */`
+    Doc,
+}
+
+/// One line of a comment
+pub(crate) struct CommentLine {
+    /// Line number (1-indexed)
+    pub(crate) number: usize,
+    /// Column where `text` starts in the source line (1-indexed, in characters)
+    pub(crate) column: usize,
+    /// Comment text with markers stripped
+    pub(crate) text: String,
+}
+
+/// Split source content into comments, ignoring comment markers inside literals
+pub(crate) fn lex_comments(content: &str, syntax: &LanguageSyntax) -> Vec<Comment> {
+    let mut lexer = Lexer::new(content, syntax);
+    lexer.run();
+    lexer.comments
+}
+
+struct Lexer<'a> {
+    content: &'a str,
+    syntax: &'a LanguageSyntax,
+    /// String delimiters, longest first so `"""` wins over `"`
+    string_delimiters: Vec<&'a str>,
+    /// Raw string delimiters, longest first
+    raw_string_delimiters: Vec<&'a str>,
+    /// Byte offset of the next character to read
+    pos: usize,
+    /// Current line number (1-indexed)
+    line: usize,
+    /// Whether code appeared on the current line before `pos`
+    line_has_code: bool,
+    /// Marker, kind and last line of the full-line comment group being collected
+    open_line_group: Option<(&'a str, CommentKind, usize)>,
+    /// Earliest offset from which each closing delimiter (and whether escapes
+    /// apply) was searched for and not found, so a stray quote doesn't rescan
+    /// the rest of the file every time one is seen
+    unclosed: HashMap<(String, bool), usize>,
+    comments: Vec<Comment>,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(content: &'a str, syntax: &'a LanguageSyntax) -> Self {
+        let longest_first = |delimiters: &'a [String]| {
+            let mut sorted: Vec<&str> = delimiters
+                .iter()
+                .map(String::as_str)
+                .filter(|d| !d.is_empty())
+                .collect();
+            sorted.sort_by_key(|d| std::cmp::Reverse(d.len()));
+            sorted
+        };
+
+        Self {
+            content,
+            syntax,
+            string_delimiters: longest_first(&syntax.string_delimiters),
+            raw_string_delimiters: longest_first(&syntax.raw_string_delimiters),
+            pos: 0,
+            line: 1,
+            line_has_code: false,
+            open_line_group: None,
+            unclosed: HashMap::new(),
+            comments: Vec::new(),
+        }
+    }
+
+    fn run(&mut self) {
+        while let Some(c) = self.content[self.pos..].chars().next() {
+            let rest = &self.content[self.pos..];
+
+            if c == '\n' {
+                self.line += 1;
+                self.line_has_code = false;
+                self.pos += 1;
+            } else if c.is_whitespace() {
+                self.pos += c.len_utf8();
+            } else if let Some((start, end)) = self.match_block_comment(rest) {
+                self.lex_block_comment(start, end);
+            } else if let Some(marker) = self.match_line_comment(rest) {
+                self.lex_line_comment(marker);
+            } else if let Some(len) = self.literal_len(rest) {
+                self.skip_code(len);
+            } else {
+                self.skip_code(c.len_utf8());
+            }
+        }
+    }
+
+    fn match_block_comment(&self, rest: &str) -> Option<(&'a str, &'a str)> {
+        self.syntax
+            .block_comments
+            .iter()
+            .find(|(start, _)| !start.is_empty() && rest.starts_with(start.as_str()))
+            .map(|(start, end)| (start.as_str(), end.as_str()))
+    }
+
+    fn match_line_comment(&self, rest: &str) -> Option<&'a str> {
+        if self.syntax.comments_after_whitespace && !self.after_whitespace() {
+            return None;
+        }
+
+        self.syntax
+            .line_comments
+            .iter()
+            .find(|marker| !marker.is_empty() && rest.starts_with(marker.as_str()))
+            .map(String::as_str)
+    }
+
+    /// Whether the current position is at line start or follows whitespace
+    fn after_whitespace(&self) -> bool {
+        !matches!(self.content[..self.pos].chars().next_back(), Some(c) if !c.is_whitespace())
+    }
+
+    /// Consume a line comment and add it to the current group or start a new one
+    fn lex_line_comment(&mut self, marker: &'a str) {
+        let start = self.pos + marker.len();
+        let end = self.content[start..]
+            .find('\n')
+            .map(|offset| start + offset)
+            .unwrap_or(self.content.len());
+        let (stripped, doc, text) = strip_doc_marker(&self.content[start..end], marker);
+        let kind = if doc {
+            CommentKind::Doc
+        } else {
+            CommentKind::Line
+        };
+        let line = CommentLine {
+            number: self.line,
+            column: self.column_at(start + stripped),
+            text: text.to_string(),
+        };
+
+        // Trailing comments (after code) always stand alone
+        let trailing = self.line_has_code;
+        let continues = !trailing
+            && self
+                .open_line_group
+                .is_some_and(|(open_marker, open_kind, last_line)| {
+                    open_marker == marker && open_kind == kind && last_line + 1 == self.line
+                });
+
+        match self.comments.last_mut() {
+            Some(comment) if continues => comment.lines.push(line),
+            _ => self.comments.push(Comment {
+                kind,
+                lines: vec![line],
+            }),
+        }
+
+        self.open_line_group = (!trailing).then_some((marker, kind, self.line));
+        self.pos = end;
+    }
+
+    /// Consume a block comment, which may span several lines
+    fn lex_block_comment(&mut self, start: &str, end: &str) {
+        let body_start = self.pos + start.len();
+        let (body_end, after) =
+            match self.block_comment_close(&self.content[body_start..], start, end) {
+                Some(offset) => (body_start + offset, body_start + offset + end.len()),
+                None => (self.content.len(), self.content.len()),
+            };
+        let body = &self.content[body_start..body_end];
+
+        let mut line_start = body_start;
+        let mut kind = CommentKind::Block;
+        let mut lines = Vec::new();
+        for (index, raw) in body.split('\n').enumerate() {
+            // `/**` and `/*!` open doc comments
+            let opener = if index == 0 {
+                raw.len() - raw.trim_start_matches(['*', '!']).len()
+            } else {
+                0
+            };
+            if opener > 0 {
+                kind = CommentKind::Doc;
+            }
+            let (stripped, text) = strip_block_decoration(&raw[opener..]);
+            lines.push(CommentLine {
+                number: self.line + index,
+                column: self.column_at(line_start + opener + stripped),
+                text: text.to_string(),
+            });
+            line_start += raw.len() + 1;
+        }
+        self.comments.push(Comment { kind, lines });
+
+        self.line += body.matches('\n').count();
+        self.open_line_group = None;
+        self.pos = after;
+    }
+
+    /// Offset of the delimiter that closes a block comment body, skipping
+    /// nested comments when the language allows them
+    fn block_comment_close(&self, body: &str, start: &str, end: &str) -> Option<usize> {
+        if !self.syntax.nested_block_comments {
+            return body.find(end);
+        }
+
+        let mut depth = 0;
+        let mut index = 0;
+        while let Some(c) = body[index..].chars().next() {
+            let rest = &body[index..];
+            if rest.starts_with(end) {
+                if depth == 0 {
+                    return Some(index);
+                }
+                depth -= 1;
+                index += end.len();
+            } else if rest.starts_with(start) {
+                depth += 1;
+                index += start.len();
+            } else {
+                index += c.len_utf8();
+            }
+        }
+
+        None
+    }
+
+    /// Column (1-indexed, in characters) of a byte offset in the content
+    fn column_at(&self, offset: usize) -> usize {
+        let line_start = self.content[..offset]
+            .rfind('\n')
+            .map_or(0, |newline| newline + 1);
+        self.content[line_start..offset].chars().count() + 1
+    }
+
+    /// Skip over code (including literals), tracking line numbers
+    fn skip_code(&mut self, len: usize) {
+        let skipped = &self.content[self.pos..self.pos + len];
+        self.line += skipped.matches('\n').count();
+        self.line_has_code = true;
+        self.pos += len;
+    }
+
+    /// Length of the string, raw string or character literal starting at `rest`, if any
+    fn literal_len(&mut self, rest: &str) -> Option<usize> {
+        if let Some(style) = self.syntax.raw_strings {
+            let len = match style {
+                RawStringStyle::Rust => self.rust_raw_string_len(rest),
+                RawStringStyle::Cpp => self.cpp_raw_string_len(rest),
+            };
+            if len.is_some() {
+                return len;
+            }
+        }
+
+        if let Some(delimiter) = self
+            .raw_string_delimiters
+            .iter()
+            .find(|d| rest.starts_with(**d))
+            .copied()
+        {
+            return self.string_len(delimiter, false);
+        }
+
+        if let Some(delimiter) = self
+            .string_delimiters
+            .iter()
+            .find(|d| rest.starts_with(**d))
+            .copied()
+        {
+            return self.string_len(delimiter, true);
+        }
+
+        if self.syntax.char_literals && rest.starts_with('\'') {
+            return char_literal_len(rest);
+        }
+
+        None
+    }
+
+    /// Length of the string opened by `delimiter` at the current position, or
+    /// `None` if it never closes
+    ///
+    /// A single-character string must close on the same line when the language
+    /// requires it.
+    fn string_len(&mut self, delimiter: &str, escapes: bool) -> Option<usize> {
+        let body_start = self.pos + delimiter.len();
+        let single_line = self.syntax.single_line_strings && delimiter.chars().count() == 1;
+        let body_end = if single_line {
+            self.content[body_start..]
+                .find('\n')
+                .map_or(self.content.len(), |offset| body_start + offset)
+        } else {
+            self.content.len()
+        };
+
+        let close = self.find_closing(body_start, body_end, delimiter, escapes)?;
+        Some(close + delimiter.len() - self.pos)
+    }
+
+    /// Offset of the first `closing` between `from` and `to`, skipping
+    /// backslash-escaped characters when `escapes` is set
+    ///
+    /// Searches that run to the end of the content and fail are remembered, so
+    /// later searches for the same delimiter further on return at once.
+    fn find_closing(
+        &mut self,
+        from: usize,
+        to: usize,
+        closing: &str,
+        escapes: bool,
+    ) -> Option<usize> {
+        let key = (closing.to_string(), escapes);
+        if self
+            .unclosed
+            .get(&key)
+            .is_some_and(|unclosed| *unclosed <= from)
+        {
+            return None;
+        }
+
+        let text = &self.content[from..to];
+        let found = if escapes {
+            escaped_find(text, closing)
+        } else {
+            text.find(closing)
+        };
+        if found.is_none() && to == self.content.len() {
+            self.unclosed.insert(key, from);
+        }
+        found.map(|offset| from + offset)
+    }
+
+    /// The identifier characters immediately before the current position
+    fn preceding_identifier(&self) -> &str {
+        let before = &self.content[..self.pos];
+        let start = before
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| c.is_alphanumeric() || *c == '_')
+            .last()
+            .map(|(index, _)| index)
+            .unwrap_or(before.len());
+        &before[start..]
+    }
+
+    /// Rust raw strings: `r"..."`, `r#"..."#`, `br"..."`, `cr"..."`
+    fn rust_raw_string_len(&mut self, rest: &str) -> Option<usize> {
+        if !self.preceding_identifier().is_empty() {
+            return None;
+        }
+
+        let after_prefix = rest
+            .strip_prefix("br")
+            .or_else(|| rest.strip_prefix("cr"))
+            .or_else(|| rest.strip_prefix('r'))?;
+        let hashes = after_prefix.chars().take_while(|c| *c == '#').count();
+        let body = after_prefix[hashes..].strip_prefix('"')?;
+
+        let closing = format!("\"{}", "#".repeat(hashes));
+        let body_start = self.pos + rest.len() - body.len();
+        let close = self.find_closing(body_start, self.content.len(), &closing, false)?;
+        Some(close + closing.len() - self.pos)
+    }
+
+    /// C++ raw strings: `R"delim(...)delim"`, optionally prefixed by `u8`, `u`, `U` or `L`
+    fn cpp_raw_string_len(&mut self, rest: &str) -> Option<usize> {
+        if !matches!(self.preceding_identifier(), "" | "u8" | "u" | "U" | "L") {
+            return None;
+        }
+
+        let after_prefix = rest.strip_prefix("R\"")?;
+        let open_paren = after_prefix.find('(')?;
+        let delimiter = &after_prefix[..open_paren];
+        if delimiter.len() > 16
+            || delimiter.contains(|c: char| c.is_whitespace() || c == ')' || c == '\\')
+        {
+            return None;
+        }
+
+        let body = &after_prefix[open_paren + 1..];
+        let closing = format!("){}\"", delimiter);
+        let body_start = self.pos + rest.len() - body.len();
+        let close = self.find_closing(body_start, self.content.len(), &closing, false)?;
+        Some(close + closing.len() - self.pos)
+    }
+}
+
+/// Offset of the first `delimiter` in `text` that is not escaped by a backslash
+fn escaped_find(text: &str, delimiter: &str) -> Option<usize> {
+    let mut chars = text.char_indices();
+
+    while let Some((index, c)) = chars.next() {
+        if c == '\\' {
+            chars.next();
+        } else if text[index..].starts_with(delimiter) {
+            return Some(index);
+        }
+    }
+
+    None
+}
+
+/// Length of a Rust character literal, or `None` when the quote starts a lifetime or label
+fn char_literal_len(rest: &str) -> Option<usize> {
+    let body = &rest[1..];
+    let mut chars = body.char_indices();
+    let (_, first) = chars.next()?;
+
+    if first == '\\' {
+        // Escapes are short: '\n', '\'', '\x7f', '\u{10FFFF}'
+        let (index, escaped) = chars.next()?;
+        let after = index + escaped.len_utf8();
+        let (close, _) = body[after..]
+            .char_indices()
+            .take(9)
+            .find(|(_, c)| *c == '\'')?;
+        return Some(1 + after + close + 1);
+    }
+
+    let (index, second) = chars.next()?;
+    (second == '\'').then_some(1 + index + 1)
+}
+
+/// Strip doc comment markers (`///`, `//!`) and repeated markers (`##`, `;;;`)
+/// left after the line comment marker itself
+///
+/// Returns the number of bytes stripped from the start, whether a doc marker
+/// was present and the remaining text.
+fn strip_doc_marker<'t>(text: &'t str, marker: &str) -> (usize, bool, &'t str) {
+    let repeated = marker
+        .chars()
+        .last()
+        .map_or(text, |last| text.trim_start_matches(last));
+    let unmarked = repeated.strip_prefix('!').unwrap_or(repeated);
+    let doc = unmarked.len() < text.len();
+    let stripped = unmarked.trim_start();
+    (text.len() - stripped.len(), doc, stripped.trim_end())
+}
+
+/// Strip the leading `*` decoration used on block comment continuation lines
+///
+/// Returns the number of bytes stripped from the start and the remaining text.
+fn strip_block_decoration(text: &str) -> (usize, &str) {
+    let trimmed = text.trim_start();
+    let stripped = trimmed.strip_prefix('*').unwrap_or(trimmed).trim_start();
+    (text.len() - stripped.len(), stripped.trim_end())
+}
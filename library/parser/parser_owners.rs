@@ -0,0 +1,112 @@
+//! Owner alias resolution for TODO assignees
+//!
+//! Aliases use a `.mailmap`-style format: each line names the canonical owner,
+//! followed by the handles (in angle brackets) that refer to the same person.
+//!
+//! ```text
+//! # Canonical owner followed by their aliases
+//! Alice Smith <alice> <asmith> <alice.smith@example.com>
+//! <bob> <robert> <bobby>
+//! ```
+//!
+//! When a line has no name before the first bracket, the first bracketed handle
+//! is the canonical owner. Matching is case-insensitive and ignores a leading `@`.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use autodomd_library_common::{TodoError, TodoResult};
+
+/// Maps owner handles to their canonical owner name
+#[derive(Debug, Clone, Default)]
+pub struct OwnerAliases {
+    aliases: HashMap<String, String>,
+}
+
+impl OwnerAliases {
+    /// Load aliases from a `.mailmap`-style file
+    pub fn from_file(path: &Path) -> TodoResult<Self> {
+        let content = fs::read_to_string(path)?;
+        Self::parse(&content).map_err(|e| TodoError::Parse(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Parse aliases from `.mailmap`-style content
+    pub fn parse(content: &str) -> TodoResult<Self> {
+        let mut owner_aliases = Self::default();
+
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (name, handles) = split_alias_line(line).ok_or_else(|| {
+                TodoError::Parse(format!("line {}: expected `Name <alias> ...`", index + 1))
+            })?;
+
+            let canonical = match name {
+                Some(name) => name.to_string(),
+                None => normalize_handle(handles[0]).to_string(),
+            };
+
+            owner_aliases.add(&canonical, &canonical);
+            for handle in handles {
+                owner_aliases.add(handle, &canonical);
+            }
+        }
+
+        Ok(owner_aliases)
+    }
+
+    /// Map a handle to its canonical owner
+    pub fn add(&mut self, handle: &str, canonical: &str) {
+        self.aliases.insert(
+            normalize_handle(handle).to_lowercase(),
+            canonical.to_string(),
+        );
+    }
+
+    /// Resolve a handle to its canonical owner, or the handle itself when unknown
+    pub fn resolve(&self, handle: &str) -> String {
+        let handle = normalize_handle(handle);
+        self.aliases
+            .get(&handle.to_lowercase())
+            .cloned()
+            .unwrap_or_else(|| handle.to_string())
+    }
+
+    /// Whether no aliases are defined
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty()
+    }
+}
+
+/// Strip surrounding whitespace and a leading `@` from a handle
+fn normalize_handle(handle: &str) -> &str {
+    let handle = handle.trim();
+    handle.strip_prefix('@').unwrap_or(handle)
+}
+
+/// Split an alias line into its optional leading name and bracketed handles
+fn split_alias_line(line: &str) -> Option<(Option<&str>, Vec<&str>)> {
+    let first_bracket = line.find('<')?;
+    let name = line[..first_bracket].trim();
+
+    let mut handles = Vec::new();
+    let mut rest = &line[first_bracket..];
+    while let Some(open) = rest.find('<') {
+        let close = rest[open..].find('>')? + open;
+        let handle = rest[open + 1..close].trim();
+        if !handle.is_empty() {
+            handles.push(handle);
+        }
+        rest = &rest[close + 1..];
+    }
+
+    if handles.is_empty() {
+        return None;
+    }
+
+    Some(((!name.is_empty()).then_some(name), handles))
+}
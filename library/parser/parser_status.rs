@@ -0,0 +1,64 @@
+//! Status markers in code TODOs
+//!
+//! A description may start with a bracketed status marker such as `[wip]` or
+//! `[blocked: waiting on #42]`. Any name accepted by [`TaskStatus::from_name`]
+//! works; only `blocked` takes a reason after a `:`.
+
+use autodomd_library_common::TaskStatus;
+
+/// Status marker split off the start of a description
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct StatusMarker<'t> {
+    pub(crate) status: TaskStatus,
+    pub(crate) blocked_reason: Option<String>,
+    /// Description with the marker removed
+    pub(crate) rest: &'t str,
+}
+
+/// Split a leading `[status]` or `[blocked: reason]` marker off a description
+pub(crate) fn split_status_marker(description: &str) -> Option<StatusMarker<'_>> {
+    let inner_and_rest = description.trim_start().strip_prefix('[')?;
+    let (inner, rest) = inner_and_rest.split_once(']')?;
+
+    let (name, reason) = match inner.split_once(':') {
+        Some((name, reason)) => (name, Some(reason.trim())),
+        None => (inner, None),
+    };
+    let status = TaskStatus::from_name(name)?;
+    if reason.is_some() && status != TaskStatus::Blocked {
+        return None;
+    }
+
+    Some(StatusMarker {
+        status,
+        blocked_reason: reason
+            .filter(|reason| !reason.is_empty())
+            .map(str::to_string),
+        rest: rest.trim_start(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_markers() {
+        let wip = split_status_marker("[wip] Rotate signing keys").unwrap();
+        assert_eq!(wip.status, TaskStatus::InProgress);
+        assert_eq!(wip.rest, "Rotate signing keys");
+
+        let blocked = split_status_marker("[Blocked: waiting on #42] Drop the endpoint").unwrap();
+        assert_eq!(blocked.status, TaskStatus::Blocked);
+        assert_eq!(blocked.blocked_reason.as_deref(), Some("waiting on #42"));
+        assert_eq!(blocked.rest, "Drop the endpoint");
+
+        assert_eq!(
+            split_status_marker("[blocked] x").unwrap().blocked_reason,
+            None
+        );
+        assert!(split_status_marker("[T] generic parameter").is_none());
+        assert!(split_status_marker("[wip: soon] x").is_none());
+        assert!(split_status_marker("Fix [wip] later").is_none());
+    }
+}
@@ -0,0 +1,133 @@
+//! Tag keyword registry for TODO-style comments
+//!
+//! Comments are recognized as tasks when they start with one of the registered
+//! tag keywords (`TODO`, `FIXME`, ...). Each keyword carries the default
+//! priority assigned to the tasks it introduces.
+
+use regex::Regex;
+
+use autodomd_library_common::TaskPriority;
+
+/// A tag keyword such as `TODO` or `FIXME`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagKeyword {
+    /// The keyword as written in comments
+    pub name: String,
+    /// Default priority for tasks introduced by this keyword
+    pub priority: TaskPriority,
+}
+
+/// Built-in tag keywords and their default priorities
+const DEFAULT_TAGS: &[(&str, TaskPriority)] = &[
+    ("TODO", TaskPriority::Medium),
+    ("FIXME", TaskPriority::High),
+    ("BUG", TaskPriority::High),
+    ("HACK", TaskPriority::Medium),
+    ("XXX", TaskPriority::Medium),
+    ("OPTIMIZE", TaskPriority::Low),
+    ("NOTE", TaskPriority::Low),
+];
+
+/// Registry of tag keywords recognized in source comments
+#[derive(Debug, Clone)]
+pub struct TagRegistry {
+    keywords: Vec<TagKeyword>,
+}
+
+impl Default for TagRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        for &(name, priority) in DEFAULT_TAGS {
+            registry.register(name, priority);
+        }
+        registry
+    }
+}
+
+impl TagRegistry {
+    /// Create a registry with no keywords
+    pub fn empty() -> Self {
+        Self {
+            keywords: Vec::new(),
+        }
+    }
+
+    /// Register a keyword, replacing the priority of an existing keyword with the same name
+    pub fn register(&mut self, name: impl Into<String>, priority: TaskPriority) {
+        let name = name.into();
+        match self.keywords.iter_mut().find(|k| k.name == name) {
+            Some(existing) => existing.priority = priority,
+            None => self.keywords.push(TagKeyword { name, priority }),
+        }
+    }
+
+    /// Look up a keyword by name
+    pub fn get(&self, name: &str) -> Option<&TagKeyword> {
+        self.keywords.iter().find(|k| k.name == name)
+    }
+
+    /// All registered keywords in registration order
+    pub fn keywords(&self) -> &[TagKeyword] {
+        &self.keywords
+    }
+
+    /// Build the matchers used to find tagged comments
+    pub(crate) fn matcher(&self) -> TagMatcher<'_> {
+        // Longer names first so that e.g. `TODOS` is preferred over `TODO`
+        let mut names: Vec<&str> = self.keywords.iter().map(|k| k.name.as_str()).collect();
+        names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+        let alternation = names
+            .iter()
+            .map(|name| regex::escape(name))
+            .collect::<Vec<_>>()
+            .join("|");
+
+        let compile = |pattern: String| {
+            if names.is_empty() {
+                None
+            } else {
+                Regex::new(&pattern).ok()
+            }
+        };
+
+        TagMatcher {
+            registry: self,
+            // Match: TAG(attributes): Description, at the start of the comment
+            lenient: compile(format!(
+                r"^[\s*]*({})(?:\(([^)]*)\))?\s*:\s*(.+)",
+                alternation
+            )),
+            // Match: TAG: Description, anywhere in the comment
+            anywhere: compile(format!(r"\b({})(?:\(([^)]*)\))?\s*:\s*(.+)", alternation)),
+            // Match the strict grammar, anchored at the start of the comment
+            strict: compile(format!(r"^({})(?:\(([^)]*)\))?:\s*(\S.*)$", alternation)),
+        }
+    }
+}
+
+/// Compiled form of a [`TagRegistry`] used while parsing
+pub(crate) struct TagMatcher<'a> {
+    pub(crate) registry: &'a TagRegistry,
+    /// Finds a tag starting the comment, tolerating spaces before the `:`
+    pub(crate) lenient: Option<Regex>,
+    /// Finds a tag anywhere in the comment; only used to report tags mid-sentence
+    pub(crate) anywhere: Option<Regex>,
+    /// Accepts only comments that follow the grammar in `parser_grammar`
+    pub(crate) strict: Option<Regex>,
+}
+
+impl TagMatcher<'_> {
+    /// The registered keyword that starts `text`, compared case-insensitively
+    pub(crate) fn leading_keyword<'t>(&self, text: &'t str) -> Option<(&TagKeyword, &'t str)> {
+        let mut keywords: Vec<&TagKeyword> = self.registry.keywords().iter().collect();
+        keywords.sort_by_key(|k| std::cmp::Reverse(k.name.len()));
+
+        keywords.into_iter().find_map(|keyword| {
+            let written = text.get(..keyword.name.len())?;
+            let rest = &text[keyword.name.len()..];
+            let at_boundary = !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_');
+            (at_boundary && written.eq_ignore_ascii_case(&keyword.name))
+                .then_some((keyword, written))
+        })
+    }
+}
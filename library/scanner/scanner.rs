@@ -3,10 +3,17 @@
 //! This microcrate provides utilities for scanning source files and markdown files
 //! to extract TODO comments and tasks.
 
+mod scanner_ignore;
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
+use walkdir::{DirEntry, WalkDir};
 
 use autodomd_library_common::TodoResult;
+use autodomd_library_language::LanguageRegistry;
+
+use scanner_ignore::IgnoreList;
 
 /// Configuration for file scanning operations
 #[derive(Debug, Clone)]
@@ -17,6 +24,11 @@ pub struct ScanConfig {
     pub follow_links: bool,
     /// Maximum depth to scan (None for unlimited)
     pub max_depth: Option<usize>,
+    /// Languages whose source files are scanned
+    pub languages: LanguageRegistry,
+    /// Glob patterns for vendored code and fixtures to skip, in addition to
+    /// those listed in the root's `.autodomdignore`
+    pub ignore_patterns: Vec<String>,
 }
 
 impl Default for ScanConfig {
@@ -25,6 +37,8 @@ impl Default for ScanConfig {
             root_path: PathBuf::from("."),
             follow_links: false,
             max_depth: Some(10), // Reasonable default to prevent infinite recursion
+            languages: LanguageRegistry::default(),
+            ignore_patterns: Vec::new(),
         }
     }
 }
@@ -37,15 +51,20 @@ pub fn scan_markdown_files(config: &ScanConfig) -> TodoResult<Vec<PathBuf>> {
     }
 
     let mut files = Vec::new();
+    let ignore = IgnoreList::load(&config.root_path, &config.ignore_patterns)?;
 
     let walker = WalkDir::new(&todo_dir)
         .follow_links(config.follow_links)
         .max_depth(config.max_depth.unwrap_or(usize::MAX))
         .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0 || !is_ignored(entry.path(), &config.root_path, &ignore)
+        })
         .filter_map(|entry| entry.ok()) // Skip entries with errors
         .filter(|entry| entry.file_type().is_file())
         .filter(|entry| {
-            entry.path()
+            entry
+                .path()
                 .extension()
                 .and_then(|ext| ext.to_str())
                 .map(|ext| ext == "md")
@@ -61,39 +80,16 @@ pub fn scan_markdown_files(config: &ScanConfig) -> TodoResult<Vec<PathBuf>> {
     Ok(files)
 }
 
-/// Supported source file extensions for TODO comment scanning
-const SOURCE_EXTENSIONS: &[&str] = &[
-    "rs",    // Rust
-    "js",    // JavaScript
-    "ts",    // TypeScript
-    "jsx",   // React JavaScript
-    "tsx",   // React TypeScript
-    "py",    // Python
-    "java",  // Java
-    "c",     // C
-    "cpp",   // C++
-    "cc",    // C++
-    "cxx",   // C++
-    "h",     // C/C++ headers
-    "hpp",   // C++ headers
-    "go",    // Go
-    "rb",    // Ruby
-    "php",   // PHP
-    "swift", // Swift
-    "kt",    // Kotlin
-    "scala", // Scala
-];
-
 /// Directories to exclude from scanning
 const EXCLUDED_DIRS: &[&str] = &[
-    "target",     // Rust build artifacts
+    "target",       // Rust build artifacts
     "node_modules", // Node.js dependencies
     "__pycache__",  // Python cache
-    ".git",        // Git repository
-    ".svn",        // SVN repository
-    ".hg",         // Mercurial repository
-    ".DS_Store",   // macOS
-    "Thumbs.db",   // Windows
+    ".git",         // Git repository
+    ".svn",         // SVN repository
+    ".hg",          // Mercurial repository
+    ".DS_Store",    // macOS
+    "Thumbs.db",    // Windows
 ];
 
 /// Check if a directory should be excluded from scanning
@@ -101,44 +97,62 @@ fn should_exclude_dir(dir_name: &str) -> bool {
     EXCLUDED_DIRS.contains(&dir_name) || dir_name.starts_with('.')
 }
 
-/// Check if a file has a supported extension for TODO scanning
-fn is_supported_source_file(path: &Path) -> bool {
-    path.extension()
-        .and_then(|ext| ext.to_str())
-        .map(|ext| SOURCE_EXTENSIONS.contains(&ext))
-        .unwrap_or(false)
+/// Check if a file belongs to a registered language for TODO scanning
+///
+/// Files without an extension (e.g. `bin/deploy`) are identified by their `#!` line.
+fn is_supported_source_file(path: &Path, languages: &LanguageRegistry) -> bool {
+    if languages.is_supported(path) {
+        return true;
+    }
+
+    path.extension().is_none()
+        && read_first_line(path)
+            .map(|line| languages.for_shebang(&line).is_some())
+            .unwrap_or(false)
+}
+
+/// Read the first line of a file, if it is readable text
+fn read_first_line(path: &Path) -> Option<String> {
+    let mut line = String::new();
+    BufReader::new(File::open(path).ok()?)
+        .read_line(&mut line)
+        .ok()?;
+    Some(line)
+}
+
+/// Check a path against the ignore list, relative to the scan root
+fn is_ignored(path: &Path, root: &Path, ignore: &IgnoreList) -> bool {
+    ignore.is_ignored(path.strip_prefix(root).unwrap_or(path))
+}
+
+/// Check whether the source walk should visit an entry
+///
+/// Returning `false` for a directory prunes it, so excluded directories such as
+/// `target/` and ignored vendored code are never read.
+fn should_walk(entry: &DirEntry, root: &Path, ignore: &IgnoreList) -> bool {
+    if entry.depth() == 0 {
+        return true;
+    }
+
+    let excluded_dir =
+        entry.file_type().is_dir() && entry.file_name().to_str().is_some_and(should_exclude_dir);
+    !excluded_dir && !is_ignored(entry.path(), root, ignore)
 }
 
 /// Scan for source files that may contain TODO comments
 pub fn scan_source_files(config: &ScanConfig) -> TodoResult<Vec<PathBuf>> {
     let mut files = Vec::new();
+    let ignore = IgnoreList::load(&config.root_path, &config.ignore_patterns)?;
 
     let walker = WalkDir::new(&config.root_path)
         .follow_links(config.follow_links)
         .max_depth(config.max_depth.unwrap_or(usize::MAX))
         .into_iter()
+        .filter_entry(|entry| should_walk(entry, &config.root_path, &ignore))
         .filter_map(|entry| entry.ok()) // Skip entries with errors
         .filter(|entry| {
             // Only process files, not directories
-            if !entry.file_type().is_file() {
-                return false;
-            }
-
-            // Check if this is a supported source file
-            if !is_supported_source_file(entry.path()) {
-                return false;
-            }
-
-            // Check if any parent directory should be excluded
-            for ancestor in entry.path().ancestors().skip(1) {
-                if let Some(dir_name) = ancestor.file_name().and_then(|n| n.to_str()) {
-                    if should_exclude_dir(dir_name) {
-                        return false;
-                    }
-                }
-            }
-
-            true
+            entry.file_type().is_file() && is_supported_source_file(entry.path(), &config.languages)
         })
         .map(|entry| entry.path().to_path_buf());
 
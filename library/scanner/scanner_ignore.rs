@@ -0,0 +1,113 @@
+//! Ignore list for vendored code and fixtures
+//!
+//! Patterns use `.gitignore`-like globs relative to the scan root:
+//!
+//! - `vendor/` or `vendor` ignores every `vendor` directory
+//! - `tests/fixtures/**` ignores everything below `tests/fixtures`
+//! - `*.min.js` ignores matching files in any directory
+//!
+//! Patterns are read from `--ignore` and from an `.autodomdignore` file in the
+//! scan root, one per line, with `#` starting a comment.
+
+use std::fs;
+use std::path::Path;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+use autodomd_library_common::{TodoError, TodoResult};
+
+/// Name of the ignore file looked up in the scan root
+const IGNORE_FILE_NAME: &str = ".autodomdignore";
+
+/// Compiled set of ignore patterns
+#[derive(Debug, Clone)]
+pub(crate) struct IgnoreList {
+    globs: GlobSet,
+}
+
+impl IgnoreList {
+    /// Build the ignore list from explicit patterns plus the root's `.autodomdignore`
+    pub(crate) fn load(root: &Path, patterns: &[String]) -> TodoResult<Self> {
+        let mut all_patterns = patterns.to_vec();
+
+        let ignore_file = root.join(IGNORE_FILE_NAME);
+        if ignore_file.is_file() {
+            let content = fs::read_to_string(&ignore_file)?;
+            all_patterns.extend(parse_ignore_file(&content));
+        }
+
+        Self::new(&all_patterns)
+    }
+
+    /// Compile a list of patterns
+    pub(crate) fn new(patterns: &[String]) -> TodoResult<Self> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            for glob in expand_pattern(pattern) {
+                let glob = Glob::new(&glob).map_err(|e| {
+                    TodoError::Parse(format!("invalid ignore pattern '{}': {}", pattern, e))
+                })?;
+                builder.add(glob);
+            }
+        }
+
+        let globs = builder
+            .build()
+            .map_err(|e| TodoError::Parse(format!("invalid ignore patterns: {}", e)))?;
+        Ok(Self { globs })
+    }
+
+    /// Whether a path (relative to the scan root) is ignored
+    pub(crate) fn is_ignored(&self, relative_path: &Path) -> bool {
+        self.globs.is_match(relative_path)
+    }
+}
+
+/// Read patterns from `.autodomdignore` content, skipping blanks and comments
+fn parse_ignore_file(content: &str) -> impl Iterator<Item = String> + '_ {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+}
+
+/// Turn one ignore pattern into the globs that implement it
+fn expand_pattern(pattern: &str) -> Vec<String> {
+    let trimmed = pattern.trim().trim_end_matches('/');
+    if trimmed.is_empty() {
+        return Vec::new();
+    }
+
+    // Like .gitignore, a pattern without a slash matches at any depth
+    let anchored = match trimmed.strip_prefix('/') {
+        Some(rooted) => rooted.to_string(),
+        None if trimmed.contains('/') => trimmed.to_string(),
+        None => format!("**/{}", trimmed),
+    };
+
+    // Match the path itself and, for directories, everything below it
+    vec![anchored.clone(), format!("{}/**", anchored)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ignore_patterns() {
+        let patterns: Vec<String> =
+            parse_ignore_file("# vendored code\nvendor/\n\ntests/fixtures/**\n*.min.js\n/build\n")
+                .collect();
+        let ignore = IgnoreList::new(&patterns).unwrap();
+
+        assert!(ignore.is_ignored(Path::new("vendor/lib.rs")));
+        assert!(ignore.is_ignored(Path::new("crates/foo/vendor/lib.rs")));
+        assert!(ignore.is_ignored(Path::new("tests/fixtures/sample.rs")));
+        assert!(ignore.is_ignored(Path::new("static/app.min.js")));
+        assert!(ignore.is_ignored(Path::new("build/out.rs")));
+        assert!(!ignore.is_ignored(Path::new("src/build/mod.rs")));
+        assert!(!ignore.is_ignored(Path::new("src/vendored.rs")));
+        assert!(!ignore.is_ignored(Path::new("tests/integration.rs")));
+    }
+}